@@ -1,3 +1,250 @@
-fn main() {
-    println!("Hello, world!");
+//! rxor: encode, decode, xor and crack data from the command line
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::process::ExitCode;
+
+use rxor_tools::{break_repeating_xor, xor, Ascii, Format, Hex, Raw};
+
+const USAGE: &str = "\
+usage: rxor <command> [options] [FILE]
+
+Reads FILE (or stdin if it's missing or -) and writes the result to stdout.
+
+commands:
+  encode    convert from --from (default raw) to --to (default hex)
+  decode    convert from --from (default hex) to --to (default raw)
+  xor       xor the input with --key (formats default to raw)
+  crack     break a repeating xor cipher, printing the key to stderr and the plaintext to stdout
+            (formats default to raw)
+
+options:
+  --from FMT        input format
+  --to FMT          output format
+  --key KEY         key for xor
+  --key-format FMT  format of --key (default ascii)
+  --min N           shortest key length crack tries (default 2)
+  --max N           longest key length crack tries (default 40)
+  -h, --help        show this message
+
+formats: raw, ascii, hex, base64, base64url, base32, binary";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Command {
+    Encode,
+    Decode,
+    Xor,
+    Crack,
+}
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    command: Command,
+    from: Option<Format>,
+    to: Option<Format>,
+    key: Option<String>,
+    key_format: Option<Format>,
+    min: usize,
+    max: usize,
+    file: Option<String>,
+}
+
+/// a format name from the command line, None for raw (bytes passed through as they are)
+fn parse_format(s: &str) -> Result<Option<Format>, String> {
+    match s {
+        "raw" => Ok(None),
+        _ => Format::from_name(s).map(Some).ok_or_else(|| format!("unknown format '{s}'")),
+    }
+}
+
+/// parse input in the given format, surrounding whitespace (and any inside the text encodings)
+/// is ignored for everything but raw
+fn read_as(format: Option<Format>, bytes: Vec<u8>) -> Result<Raw, String> {
+    let Some(format) = format else {
+        return Ok(Raw::new(bytes));
+    };
+
+    let text = String::from_utf8(bytes).map_err(|_| "input isn't valid UTF-8".to_string())?;
+    let text = match format {
+        Format::Ascii => text.trim_end_matches(['\n', '\r']).to_string(),
+        _ => text.chars().filter(|c| !c.is_whitespace()).collect(),
+    };
+
+    Raw::decode_from(&text, format).ok_or_else(|| format!("input isn't valid {}", format.name()))
+}
+
+/// encode raw into the given format, text formats get a trailing newline
+fn write_as(format: Option<Format>, raw: Raw) -> Vec<u8> {
+    match format {
+        None => raw.get().to_vec(),
+        Some(format) => format!("{}\n", raw.encode_as(format)).into_bytes(),
+    }
+}
+
+/// parse the arguments after the program name. Ok(None) means help was asked for
+fn parse_args(args: &[String]) -> Result<Option<Args>, String> {
+    let mut args = args.iter();
+
+    let command = match args.next().map(String::as_str) {
+        Some("encode") => Command::Encode,
+        Some("decode") => Command::Decode,
+        Some("xor") => Command::Xor,
+        Some("crack") => Command::Crack,
+        Some("-h" | "--help") => return Ok(None),
+        Some(other) => return Err(format!("unknown command '{other}'")),
+        None => return Err("missing command".to_string()),
+    };
+
+    let (from, to) = match command {
+        Command::Encode => (None, Some(Format::Hex)),
+        Command::Decode => (Some(Format::Hex), None),
+        Command::Xor | Command::Crack => (None, None),
+    };
+
+    let mut parsed = Args {
+        command,
+        from,
+        to,
+        key: None,
+        key_format: Some(Format::Ascii),
+        min: 2,
+        max: 40,
+        file: None,
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{arg} needs a value"));
+
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--from" => parsed.from = parse_format(value()?)?,
+            "--to" => parsed.to = parse_format(value()?)?,
+            "--key" => parsed.key = Some(value()?.clone()),
+            "--key-format" => parsed.key_format = parse_format(value()?)?,
+            "--min" => parsed.min = value()?.parse().map_err(|_| "--min must be a number".to_string())?,
+            "--max" => parsed.max = value()?.parse().map_err(|_| "--max must be a number".to_string())?,
+            flag if flag.starts_with("--") => return Err(format!("unknown option '{flag}'")),
+            file if parsed.file.is_none() => parsed.file = Some(file.to_string()),
+            extra => return Err(format!("unexpected argument '{extra}'")),
+        }
+    }
+
+    if parsed.command == Command::Xor && parsed.key.is_none() {
+        return Err("xor needs --key".to_string());
+    }
+
+    if parsed.min == 0 || parsed.min > parsed.max {
+        return Err("key lengths need 0 < --min <= --max".to_string());
+    }
+
+    Ok(Some(parsed))
+}
+
+fn read_input(file: Option<&str>) -> Result<Vec<u8>, String> {
+    match file {
+        None | Some("-") => {
+            let mut buf = Vec::new();
+            io::stdin().read_to_end(&mut buf).map_err(|e| format!("reading stdin: {e}"))?;
+            Ok(buf)
+        }
+        Some(path) => fs::read(path).map_err(|e| format!("reading {path}: {e}")),
+    }
+}
+
+fn run(args: &Args) -> Result<Vec<u8>, String> {
+    let input = read_as(args.from, read_input(args.file.as_deref())?)?;
+
+    let output = match args.command {
+        Command::Encode | Command::Decode => input,
+        Command::Xor => {
+            let key = args.key.clone().unwrap_or_default().into_bytes();
+            let key = read_as(args.key_format, key)?;
+
+            xor(&input, &key).map_err(|e| e.to_string())?
+        }
+        Command::Crack => {
+            let (key, plaintext) = break_repeating_xor(&input, args.min, args.max)
+                .ok_or("input is too short for the given key lengths")?;
+
+            eprintln!("key: {}", Hex::from(key.clone()));
+            eprintln!("key (ascii): {}", Ascii::from(key.smallest_period()));
+
+            plaintext
+        }
+    };
+
+    Ok(write_as(args.to, output))
+}
+
+fn main() -> ExitCode {
+    let argv: Vec<String> = std::env::args().skip(1).collect();
+
+    let args = match parse_args(&argv) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("rxor: {e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(&args) {
+        Ok(output) => {
+            if let Err(e) = io::stdout().write_all(&output) {
+                eprintln!("rxor: writing output: {e}");
+                return ExitCode::FAILURE;
+            }
+
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("rxor: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(s: &str) -> Result<Option<Args>, String> {
+        parse_args(&s.split_whitespace().map(String::from).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let parsed = args("decode --from base64 --to ascii in.txt").unwrap().unwrap();
+        assert_eq!(parsed.command, Command::Decode);
+        assert_eq!(parsed.from, Some(Format::Base64));
+        assert_eq!(parsed.to, Some(Format::Ascii));
+        assert_eq!(parsed.file.as_deref(), Some("in.txt"));
+
+        let parsed = args("encode").unwrap().unwrap();
+        assert_eq!((parsed.from, parsed.to), (None, Some(Format::Hex)));
+
+        assert_eq!(args("--help"), Ok(None));
+        assert!(args("").is_err());
+        assert!(args("xor").is_err());
+        assert!(args("encode --to morse").is_err());
+        assert!(args("crack --min 5 --max 2").is_err());
+        assert!(args("encode a b").is_err());
+    }
+
+    #[test]
+    fn test_format_round_trip() {
+        let raw = Raw::new(b"Cooking MC's like a pound of bacon".to_vec());
+
+        assert_eq!(read_as(None, write_as(None, raw.clone())), Ok(raw.clone()));
+
+        for format in Format::ALL {
+            assert_eq!(read_as(Some(format), write_as(Some(format), raw.clone())), Ok(raw.clone()), "{}", format.name());
+        }
+
+        assert!(read_as(Some(Format::Hex), b"zz".to_vec()).is_err());
+        assert_eq!(read_as(Some(Format::Hex), b"de ad\nbe ef\n".to_vec()), Ok(Raw::new(vec![0xde, 0xad, 0xbe, 0xef])));
+    }
 }
@@ -0,0 +1,73 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rxor_tools::*;
+
+const SIZES: [(&str, usize); 2] = [("1kb", 1 << 10), ("1mb", 1 << 20)];
+
+/// English-ish input xor'd with a short key, so the attacks have something realistic to chew on
+fn input(len: usize) -> Raw {
+    let text = b"Cooking MC's like a pound of bacon, burning 'em if you ain't quick and nimble. ";
+    let plain = Raw::new(text.iter().cycle().take(len).copied().collect::<Vec<u8>>());
+
+    xor(&plain, &Raw::new(b"ICE".to_vec())).unwrap()
+}
+
+fn bench_xor(c: &mut Criterion) {
+    let mut group = c.benchmark_group("xor");
+    let key = Raw::new(b"YELLOW SUBMARINE".to_vec());
+
+    for (name, len) in SIZES {
+        let seq = input(len);
+        group.throughput(Throughput::Bytes(len as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &seq, |b, seq| b.iter(|| xor(seq, &key)));
+    }
+
+    group.finish();
+}
+
+fn bench_hamming(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hamming");
+
+    for (name, len) in SIZES {
+        let l = input(len);
+        let r = xor(&l, &Raw::new(vec![0x5a])).unwrap();
+        group.throughput(Throughput::Bytes(len as u64));
+        group.bench_function(name, |b| b.iter(|| l.hamming(&r)));
+    }
+
+    group.finish();
+}
+
+fn bench_encodings(c: &mut Criterion) {
+    let mut group = c.benchmark_group("encodings");
+
+    for (name, len) in SIZES {
+        let raw = input(len);
+        let hex = Hex::encode(&raw);
+        let base64 = Base64::encode(&raw);
+        group.throughput(Throughput::Bytes(len as u64));
+
+        group.bench_function(BenchmarkId::new("hex_encode", name), |b| b.iter(|| Hex::encode(&raw)));
+        group.bench_function(BenchmarkId::new("hex_decode", name), |b| b.iter(|| hex.decode()));
+        group.bench_function(BenchmarkId::new("base64_encode", name), |b| b.iter(|| Base64::encode(&raw)));
+        group.bench_function(BenchmarkId::new("base64_decode", name), |b| b.iter(|| base64.decode()));
+    }
+
+    group.finish();
+}
+
+fn bench_find_key_len(c: &mut Criterion) {
+    let mut group = c.benchmark_group("find_key_len");
+    // the 1mb case scores 39 lengths over the whole input, keep the run short
+    group.sample_size(10);
+
+    for (name, len) in SIZES {
+        let raw = input(len);
+        group.throughput(Throughput::Bytes(len as u64));
+        group.bench_function(name, |b| b.iter(|| find_key_len(&raw, 2, 40)));
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_xor, bench_hamming, bench_encodings, bench_find_key_len);
+criterion_main!(benches);
@@ -0,0 +1,43 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rxor_tools::*;
+
+/// the byte at a time loop xor used to be, as a baseline for the word at a time one
+fn xor_bytewise(seq: &Raw, key: &Raw) -> Raw {
+    seq.iter().zip(key.iter().cycle()).map(|(s, k)| s ^ k).collect()
+}
+
+fn bench_xor(c: &mut Criterion) {
+    let seq = Raw::new((0..(1 << 20)).map(|i| (i * 7) as u8).collect::<Vec<u8>>());
+    let mut group = c.benchmark_group("xor_1mb");
+    group.throughput(Throughput::Bytes(seq.len() as u64));
+
+    for key in ["ICE!", "YELLOW S", "ICE", "VANILLA"] {
+        let key: Raw = Ascii::new(key.to_string()).unwrap().into();
+
+        group.bench_with_input(BenchmarkId::new("bytewise", key.len()), &key, |b, key| {
+            b.iter(|| xor_bytewise(&seq, key))
+        });
+        group.bench_with_input(BenchmarkId::new("xor", key.len()), &key, |b, key| {
+            b.iter(|| xor(&seq, key))
+        });
+        group.bench_with_input(BenchmarkId::new("ct", key.len()), &key, |b, key| {
+            b.iter(|| xor_ct(&seq, key))
+        });
+    }
+
+    group.finish();
+
+    // an odd key's lcm block is 8 keys long, far more than a short input can fill
+    let seq = Raw::new((0..1000).map(|i| (i * 7) as u8).collect::<Vec<u8>>());
+    let key = Raw::new((0..1001).map(|i| (i * 31 + 1) as u8).collect::<Vec<u8>>());
+    let mut group = c.benchmark_group("xor_long_key_short_input");
+    group.throughput(Throughput::Bytes(seq.len() as u64));
+
+    group.bench_function("bytewise", |b| b.iter(|| xor_bytewise(&seq, &key)));
+    group.bench_function("xor", |b| b.iter(|| xor(&seq, &key)));
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_xor);
+criterion_main!(benches);
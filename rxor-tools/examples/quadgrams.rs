@@ -0,0 +1,63 @@
+//! Regenerates the QUADGRAMS table in src/analysis.rs from text files:
+//!
+//! ```text
+//! cargo run --example quadgrams -- alice29.txt asyoulik.txt
+//! ```
+//!
+//! Quadgrams are counted the way quadgram_score reads its input, over the run of letters left
+//! after dropping everything else and folding case, so they span word boundaries.
+
+use std::collections::HashMap;
+use std::{env, fs, process};
+
+/// how many of the most common quadgrams go in the table
+const TABLE_LEN: usize = 256;
+
+fn main() {
+    let paths: Vec<String> = env::args().skip(1).collect();
+    if paths.is_empty() {
+        eprintln!("usage: quadgrams <file>...");
+        process::exit(2);
+    }
+
+    let mut counts: HashMap<[u8; 4], u32> = HashMap::new();
+    let mut total = 0u64;
+
+    for path in &paths {
+        let text = fs::read(path).unwrap_or_else(|e| {
+            eprintln!("{}: {}", path, e);
+            process::exit(1);
+        });
+
+        let letters: Vec<u8> = text
+            .iter()
+            .filter(|b| b.is_ascii_alphabetic())
+            .map(u8::to_ascii_uppercase)
+            .collect();
+
+        for q in letters.windows(4) {
+            *counts.entry([q[0], q[1], q[2], q[3]]).or_default() += 1;
+            total += 1;
+        }
+    }
+
+    // most common first, ties broken alphabetically so the output is stable
+    let mut ranked: Vec<([u8; 4], u32)> = counts.into_iter().collect();
+    ranked.sort_by(|l, r| r.1.cmp(&l.1).then(l.0.cmp(&r.0)));
+    ranked.truncate(TABLE_LEN);
+
+    // the table is binary searched, so it goes out in byte order
+    ranked.sort_by_key(|(q, _)| *q);
+
+    println!("const QUADGRAMS: [([u8; 4], u32); {}] = [", ranked.len());
+    for row in ranked.chunks(5) {
+        let cells: Vec<String> = row
+            .iter()
+            .map(|(q, n)| format!("(*b\"{}\", {})", String::from_utf8_lossy(q), n))
+            .collect();
+        println!("    {},", cells.join(", "));
+    }
+    println!("];");
+    println!();
+    println!("const QUADGRAM_TOTAL: f32 = {}.0;", total);
+}
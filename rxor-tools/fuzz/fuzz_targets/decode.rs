@@ -0,0 +1,40 @@
+//! Feeds arbitrary strings to the hex, base64 and ASCII constructors. Nothing may panic, and
+//! anything they accept has to survive a decode/encode round trip. Run from rxor-tools/ with
+//! `cargo +nightly fuzz run decode`
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rxor_tools::{Ascii, Base64, Decode, Encode, Hex, TryDecode};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(s) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    if let Some(hex) = Hex::new(s.to_string()) {
+        let raw = hex.decode();
+        assert_eq!(Hex::encode(&raw).decode(), raw);
+
+        // an odd trailing char is padded out to a whole byte, anything else comes back as given
+        if s.len().is_multiple_of(2) {
+            assert_eq!(Hex::encode(&raw).as_str(), s.to_ascii_lowercase());
+            assert_eq!(hex.try_decode(), Ok(raw));
+        }
+    }
+
+    let _ = Hex::new_lenient(s);
+
+    if let Some(b64) = Base64::new(s.to_string()) {
+        let raw = b64.decode();
+        assert_eq!(Base64::encode(&raw).decode(), raw);
+        assert_eq!(b64.try_decode(), Ok(raw));
+    }
+
+    let _ = Base64::new_lenient(s);
+
+    if let Some(ascii) = Ascii::new(s.to_string()) {
+        let raw = ascii.decode();
+        assert_eq!(Ascii::encode(&raw).as_str(), s);
+    }
+});
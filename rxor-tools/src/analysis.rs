@@ -0,0 +1,478 @@
+//! Statistical tools for judging how plaintext-like a sequence is
+
+use crate::Raw;
+
+/// relative frequency of each letter (a-z) in English text, as english_score assumes. Sums to 1
+pub const ENGLISH_FREQ: [f32; 26] = [
+    0.08167, 0.01492, 0.02782, 0.04253, 0.12702, 0.02228, 0.02015, 0.06094, 0.06966,
+    0.00153, 0.00772, 0.04025, 0.02406, 0.06749, 0.07507, 0.01929, 0.00095, 0.05987,
+    0.06327, 0.09056, 0.02758, 0.00978, 0.02360, 0.00150, 0.01974, 0.00074,
+];
+
+/// fraction of characters in English text that are spaces
+pub const ENGLISH_SPACE_FREQ: f32 = 0.18;
+
+/// fraction of characters in English text that are digits, punctuation or line breaks
+const ENGLISH_OTHER_FREQ: f32 = 0.04;
+
+/// score added for every byte that would never show up in plaintext
+const NON_PRINTABLE_PENALTY: f32 = 1000.0;
+
+/// chi-squared statistic of a sequence against English letter frequencies (lower is more English-like)
+pub fn english_score(raw: &Raw) -> f32 {
+    freq_score(raw, &ENGLISH_FREQ, ENGLISH_SPACE_FREQ)
+}
+
+/// english_score against another language: table holds its letter frequencies (a-z, summing to
+/// 1 with every entry above 0, accents folded into the plain letter) and space_freq the fraction
+/// of its text that's spaces (under 0.96). Punctuation and non printable bytes are judged the
+/// same as in english_score. Pass it to the *_with attacks in a closure, e.g.
+/// `break_single_byte_with(&cipher, |r| freq_score(r, &FRENCH, 0.16))`
+pub fn freq_score(raw: &Raw, table: &[f32; 26], space_freq: f32) -> f32 {
+    // 26 letters (case folded), then space, then any other printable character
+    let mut counts = [0usize; 28];
+    let mut penalty = 0.0;
+
+    for b in raw.iter() {
+        match b {
+            b'a'..=b'z' => counts[(b - b'a') as usize] += 1,
+            b'A'..=b'Z' => counts[(b - b'A') as usize] += 1,
+            b' ' => counts[26] += 1,
+            b'\n' | b'\r' | b'\t' | 0x21..=0x7e => counts[27] += 1,
+            _ => penalty += NON_PRINTABLE_PENALTY,
+        }
+    }
+
+    let n = counts.iter().sum::<usize>() as f32;
+
+    if n == 0.0 {
+        return penalty;
+    }
+
+    let letters = 1.0 - space_freq - ENGLISH_OTHER_FREQ;
+    let mut chi2 = 0.0;
+
+    for (i, observed) in counts.iter().enumerate() {
+        let expected = match i {
+            0..=25 => n * letters * table[i],
+            26 => n * space_freq,
+            _ => n * ENGLISH_OTHER_FREQ,
+        };
+
+        let diff = *observed as f32 - expected;
+        chi2 += diff * diff / expected;
+    }
+
+    chi2 + penalty
+}
+
+/// the 256 most common quadgrams in alice29.txt (Alice's Adventures in Wonderland) and
+/// asyoulik.txt (As You Like It) from the Canterbury corpus (Arnold and Bell, 1997), sorted so
+/// they can be binary searched, with their counts out of QUADGRAM_TOTAL quadgrams (letters only,
+/// case folded, spanning word boundaries). Regenerate with
+/// `cargo run --example quadgrams -- alice29.txt asyoulik.txt`. The texts are short enough that
+/// a few character names (ALIC, ROSA) make the list
+const QUADGRAMS: [([u8; 4], u32); 256] = [
+    (*b"ABOU", 108), (*b"AGAI", 122), (*b"AIDA", 133), (*b"AIDT", 271), (*b"ALIC", 400),
+    (*b"ALIN", 280), (*b"ALLT", 105), (*b"ANDA", 152), (*b"ANDB", 95), (*b"ANDH", 100),
+    (*b"ANDI", 126), (*b"ANDO", 213), (*b"ANDS", 223), (*b"ANDT", 319), (*b"ANDW", 144),
+    (*b"AQUE", 83), (*b"ASTH", 102), (*b"ATHE", 167), (*b"ATTE", 121), (*b"ATTH", 184),
+    (*b"BOUT", 101), (*b"BUTT", 83), (*b"CELI", 131), (*b"CHST", 89), (*b"COME", 166),
+    (*b"COUL", 111), (*b"COUR", 90), (*b"DALI", 177), (*b"DAND", 136), (*b"DNOT", 122),
+    (*b"DOWN", 117), (*b"DSHE", 88), (*b"DTHE", 612), (*b"DUKE", 107), (*b"DYOU", 118),
+    (*b"EAND", 282), (*b"EART", 100), (*b"ECOU", 97), (*b"EDTH", 142), (*b"EDTO", 132),
+    (*b"EFOR", 201), (*b"EHAD", 100), (*b"ELIA", 126), (*b"ENOT", 82), (*b"ENTE", 82),
+    (*b"ENTH", 126), (*b"ENTO", 82), (*b"EOFT", 93), (*b"EQUE", 91), (*b"ERAN", 96),
+    (*b"ERES", 94), (*b"ERSE", 118), (*b"ERTH", 169), (*b"ESAI", 137), (*b"ESHE", 103),
+    (*b"ESTH", 82), (*b"ETHA", 162), (*b"ETHE", 397), (*b"ETHI", 120), (*b"EVER", 257),
+    (*b"EWAS", 166), (*b"EWIT", 88), (*b"EYOU", 218), (*b"FORE", 146), (*b"FORT", 121),
+    (*b"FTHE", 258), (*b"FYOU", 89), (*b"GAIN", 128), (*b"GOOD", 143), (*b"GTHE", 89),
+    (*b"HALL", 126), (*b"HAND", 122), (*b"HATH", 119), (*b"HATI", 159), (*b"HATS", 146),
+    (*b"HATT", 174), (*b"HAVE", 258), (*b"HEAD", 92), (*b"HEAR", 139), (*b"HEBE", 96),
+    (*b"HECA", 92), (*b"HECO", 127), (*b"HEDO", 82), (*b"HEHA", 155), (*b"HEIR", 88),
+    (*b"HEMA", 93), (*b"HEMO", 132), (*b"HEQU", 87), (*b"HERA", 120), (*b"HERE", 441),
+    (*b"HERS", 186), (*b"HESA", 93), (*b"HESE", 91), (*b"HETH", 83), (*b"HEWA", 113),
+    (*b"HICH", 108), (*b"HING", 253), (*b"HINK", 94), (*b"HOUG", 129), (*b"HOUL", 86),
+    (*b"HSTO", 89), (*b"HTHE", 137), (*b"IDAL", 118), (*b"IDTH", 242), (*b"IGHT", 201),
+    (*b"INGA", 180), (*b"INGI", 125), (*b"INGO", 132), (*b"INGS", 152), (*b"INGT", 232),
+    (*b"INTH", 239), (*b"INTO", 108), (*b"ITHA", 127), (*b"ITHE", 106), (*b"ITTL", 151),
+    (*b"ITWA", 96), (*b"KING", 206), (*b"KNOW", 156), (*b"LAND", 235), (*b"LICE", 401),
+    (*b"LIKE", 186), (*b"LIND", 278), (*b"LING", 99), (*b"LITT", 151), (*b"LIVE", 110),
+    (*b"LLTH", 139), (*b"LOOK", 142), (*b"LOVE", 186), (*b"LTHE", 98), (*b"MAKE", 86),
+    (*b"MENT", 85), (*b"METH", 105), (*b"MORE", 130), (*b"MOUS", 86), (*b"MUCH", 89),
+    (*b"MUST", 88), (*b"NAND", 134), (*b"NDER", 117), (*b"NDSH", 87), (*b"NDTH", 325),
+    (*b"NEVE", 85), (*b"NGTH", 137), (*b"NGTO", 109), (*b"NING", 106), (*b"NOTH", 119),
+    (*b"NTER", 107), (*b"NTHE", 421), (*b"NYOU", 106), (*b"OFTH", 272), (*b"OHER", 86),
+    (*b"ONTH", 119), (*b"ORLA", 168), (*b"ORTH", 106), (*b"OSAL", 277), (*b"OTHE", 352),
+    (*b"OUCH", 106), (*b"OUGH", 203), (*b"OULD", 363), (*b"OUND", 137), (*b"OURS", 93),
+    (*b"OUSE", 151), (*b"OUTH", 132), (*b"OUTO", 104), (*b"OVER", 100), (*b"OYOU", 99),
+    (*b"QUEE", 92), (*b"QUES", 112), (*b"RAND", 120), (*b"REAT", 105), (*b"REST", 121),
+    (*b"RETH", 87), (*b"RING", 88), (*b"RLAN", 169), (*b"ROSA", 276), (*b"RSEL", 111),
+    (*b"RTHA", 85), (*b"RTHE", 196), (*b"RYOU", 92), (*b"SAID", 475), (*b"SALI", 299),
+    (*b"SAND", 239), (*b"SELF", 165), (*b"SHAL", 112), (*b"SHEH", 83), (*b"SHES", 119),
+    (*b"SHEW", 119), (*b"SHOU", 101), (*b"SNOT", 83), (*b"SOME", 136), (*b"SSHE", 113),
+    (*b"STER", 98), (*b"STHA", 106), (*b"STHE", 270), (*b"STON", 104), (*b"SYOU", 112),
+    (*b"TAND", 184), (*b"THAN", 163), (*b"THAT", 679), (*b"THEB", 104), (*b"THEC", 188),
+    (*b"THED", 198), (*b"THEE", 167), (*b"THEF", 133), (*b"THEG", 129), (*b"THEH", 119),
+    (*b"THEI", 103), (*b"THEK", 83), (*b"THEL", 125), (*b"THEM", 347), (*b"THEN", 211),
+    (*b"THEP", 122), (*b"THEQ", 83), (*b"THER", 732), (*b"THES", 237), (*b"THET", 122),
+    (*b"THEW", 191), (*b"THEY", 231), (*b"THIN", 339), (*b"THIS", 315), (*b"THOU", 319),
+    (*b"THTH", 103), (*b"TIME", 135), (*b"TING", 140), (*b"TION", 149), (*b"TOBE", 97),
+    (*b"TOHE", 91), (*b"TONE", 169), (*b"TOTH", 176), (*b"TOUC", 96), (*b"TSHE", 126),
+    (*b"TTER", 180), (*b"TTHE", 391), (*b"TTLE", 174), (*b"TWAS", 132), (*b"TYOU", 149),
+    (*b"UCHS", 99), (*b"UGHT", 182), (*b"UTTH", 98), (*b"VERS", 86), (*b"VERY", 250),
+    (*b"WELL", 154), (*b"WENT", 95), (*b"WERE", 141), (*b"WHAT", 250), (*b"WHEN", 137),
+    (*b"WHIC", 108), (*b"WILL", 235), (*b"WITH", 446), (*b"WOUL", 173), (*b"YAND", 103),
+    (*b"YOUA", 88), (*b"YOUR", 321), (*b"YOUS", 94), (*b"YOUT", 132), (*b"YOUW", 89),
+    (*b"YTHE", 126),
+];
+
+const QUADGRAM_TOTAL: f32 = 201441.0;
+
+/// count given to quadgrams that aren't in the table
+const QUADGRAM_FLOOR: f32 = 0.01;
+
+/// average negative log10 probability of the sequence's letter quadgrams (lower is more
+/// English-like). Looks at runs of letters rather than single letter counts, so it tells apart
+/// short candidates that have similar letter frequencies but aren't actually words
+pub fn quadgram_score(raw: &Raw) -> f32 {
+    let mut penalty = 0.0;
+    let mut symbols = 0;
+    let mut letters = Vec::new();
+
+    for b in raw.iter() {
+        match b {
+            b'a'..=b'z' | b'A'..=b'Z' => letters.push(b.to_ascii_uppercase()),
+            b' ' | b'\n' | b'\r' | b'\t' => {}
+            0x21..=0x7e => symbols += 1,
+            _ => penalty += NON_PRINTABLE_PENALTY,
+        }
+    }
+
+    let floor = -(QUADGRAM_FLOOR / QUADGRAM_TOTAL).log10();
+
+    // no bytes to share the symbol charge between
+    if raw.is_empty() {
+        return floor + penalty;
+    }
+
+    // quadgrams only see letters, so digits and punctuation are charged separately: otherwise
+    // a key that turns every space into a quote would score as well as the right one
+    let symbols = floor * symbols as f32 / raw.len() as f32;
+
+    // nothing to judge, so as unlikely as an unknown quadgram
+    if letters.len() < 4 {
+        return floor + symbols + penalty;
+    }
+
+    let total: f32 = letters
+        .windows(4)
+        .map(|q| {
+            QUADGRAMS
+                .binary_search_by(|(gram, _)| gram.as_slice().cmp(q))
+                .map_or(floor, |i| -(QUADGRAMS[i].1 as f32 / QUADGRAM_TOTAL).log10())
+        })
+        .sum();
+
+    total / (letters.len() - 3) as f32 + symbols + penalty
+}
+
+/// fraction of the sequence that couldn't be part of base64 text (lower is more base64-like),
+/// for cracking xor'd plaintext that is itself base64. Padding and line breaks are allowed. An
+/// example of a scorer to pass to the *_with attacks in place of english_score. Keys that only
+/// flip letter case (0x20) keep letters in the alphabet, so it needs a few digits or symbols in
+/// the plaintext to tell those apart
+pub fn base64_score(raw: &Raw) -> f32 {
+    if raw.is_empty() {
+        return 0.0;
+    }
+
+    let outside = raw
+        .iter()
+        .filter(|b| !matches!(b, b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'+' | b'/' | b'=' | b'\n' | b'\r'))
+        .count();
+
+    outside as f32 / raw.len() as f32
+}
+
+impl Raw {
+    /// how many times each byte value appears
+    pub fn byte_histogram(&self) -> [usize; 256] {
+        let mut counts = [0usize; 256];
+
+        for b in self.iter() {
+            counts[*b as usize] += 1;
+        }
+
+        counts
+    }
+
+    /// chance that two bytes picked at random from different positions are equal. Bytes are
+    /// compared as is (no case folding), so English text lands somewhere near 0.06-0.08 while
+    /// uniformly random bytes sit at 1/256. 0.0 for sequences shorter than 2 bytes
+    pub fn index_of_coincidence(&self) -> f32 {
+        let n = self.len();
+
+        if n < 2 {
+            return 0.0;
+        }
+
+        let pairs: usize = self.byte_histogram().iter().map(|c| c * c.saturating_sub(1)).sum();
+
+        pairs as f32 / (n * (n - 1)) as f32
+    }
+
+    /// fraction of bytes that are printable ASCII or whitespace (\n, \r, \t), 0.0 if empty. A
+    /// cheap way to throw out candidate decryptions before scoring them properly
+    pub fn printable_ratio(&self) -> f32 {
+        if self.is_empty() {
+            return 0.0;
+        }
+
+        let printable = self.iter().filter(|b| matches!(b, 0x20..=0x7e | b'\n' | b'\r' | b'\t')).count();
+
+        printable as f32 / self.len() as f32
+    }
+
+    /// shannon entropy of the byte distribution in bits per byte, from 0.0 (one repeated value,
+    /// or empty) to 8.0 (every value equally common). Random, encrypted or compressed data sits
+    /// near 8, English text around 4-5
+    pub fn shannon_entropy(&self) -> f32 {
+        let n = self.len() as f32;
+
+        self.byte_histogram()
+            .iter()
+            .filter(|c| **c > 0)
+            .map(|c| {
+                let p = *c as f32 / n;
+                -p * p.log2()
+            })
+            .sum()
+    }
+
+    /// how many whole block_size blocks repeat an earlier block. Repetitive plaintext under a
+    /// key whose length divides block_size repeats in the ciphertext too, so this lights up for
+    /// the right key length multiples (and for ECB). Panics if block_size is 0
+    pub fn repeated_blocks(&self, block_size: usize) -> usize {
+        let blocks: Vec<&[u8]> = self.get().chunks_exact(block_size).collect();
+        let unique: std::collections::HashSet<&[u8]> = blocks.iter().copied().collect();
+
+        blocks.len() - unique.len()
+    }
+}
+
+/// shortest input looks_xor_encrypted will judge
+const MIN_XOR_CHECK_LEN: usize = 32;
+
+/// longest key length looks_xor_encrypted tries
+const MAX_XOR_CHECK_KEY_LEN: usize = 40;
+
+/// average column index of coincidence above which a key length is taken as plausible, halfway
+/// between random bytes (~0.004) and English (~0.067)
+const XOR_CHECK_IOC: f32 = 0.035;
+
+/// guess whether a buffer is repeating xor ciphertext of text, as opposed to plaintext or random
+/// (encrypted with something stronger, compressed) data. Checks that:
+/// - it isn't mostly printable already
+/// - its byte entropy isn't near the 8 bits of random data
+/// - for some key length up to 40 the columns have an index of coincidence close to text's,
+///   rather than the flat 1/256 of random data
+///
+/// Only a heuristic. Inputs under 32 bytes are always false, as are keys so long that each
+/// column gets fewer than 8 bytes. Structured binary plaintext (lots of zeros, repeated records)
+/// xor'd with a key passes just like text, and so does unencrypted binary with the same traits
+pub fn looks_xor_encrypted(raw: &Raw) -> bool {
+    if raw.len() < MIN_XOR_CHECK_LEN {
+        return false;
+    }
+
+    if raw.printable_ratio() > 0.95 {
+        return false;
+    }
+
+    if raw.shannon_entropy() > 7.9 {
+        return false;
+    }
+
+    // keep every column at 8 bytes or more, shorter ones make the IoC too noisy
+    let hlim = MAX_XOR_CHECK_KEY_LEN.min(raw.len() / 8);
+
+    crate::find_key_len_ioc(raw, 1, hlim).iter().any(|(_, ioc)| *ioc > XOR_CHECK_IOC)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Ascii, Base64, Encode};
+
+    #[test]
+    fn test_english_score() {
+        let english: Raw = Ascii::new("Now that the party is jumping".to_string()).unwrap().into();
+        let shouty: Raw = Ascii::new("NOW THAT THE PARTY IS JUMPING".to_string()).unwrap().into();
+        let symbols: Raw = Ascii::new("N#w t%at t*e p&rty !s j@mp^ng".to_string()).unwrap().into();
+        let binary = Raw::new(vec![0x00, 0x01, 0x02, 0x80, 0xff]);
+
+        // case is folded
+        assert_eq!(english_score(&english), english_score(&shouty));
+
+        assert!(english_score(&english) < english_score(&symbols));
+        assert!(english_score(&symbols) < english_score(&binary));
+        assert_eq!(english_score(&Raw::new(vec![])), 0.0);
+    }
+
+    #[test]
+    fn test_freq_score() {
+        let english: Raw = Ascii::new("Now that the party is jumping".to_string()).unwrap().into();
+        assert_eq!(freq_score(&english, &ENGLISH_FREQ, ENGLISH_SPACE_FREQ), english_score(&english));
+        assert!((ENGLISH_FREQ.iter().sum::<f32>() - 1.0).abs() < 0.001);
+
+        // a table that expects nothing but z's prefers z's
+        let mut zs = [0.0001; 26];
+        zs[25] = 1.0 - 25.0 * 0.0001;
+        let buzz: Raw = Ascii::new("zzz zzzz zz zzz".to_string()).unwrap().into();
+        assert!(freq_score(&buzz, &zs, 0.2) < freq_score(&english, &zs, 0.2));
+        assert!(english_score(&english) < english_score(&buzz));
+    }
+
+    #[test]
+    fn test_freq_score_cracks_other_languages() {
+        // French letter frequencies, accents folded in
+        const FRENCH: [f32; 26] = [
+            0.0764, 0.0090, 0.0326, 0.0367, 0.1472, 0.0107, 0.0087, 0.0074, 0.0753, 0.0061,
+            0.0005, 0.0546, 0.0297, 0.0710, 0.0580, 0.0252, 0.0136, 0.0669, 0.0795, 0.0724,
+            0.0631, 0.0184, 0.0005, 0.0043, 0.0013, 0.0033,
+        ];
+
+        let plain: Raw = Ascii::new("Il etait une fois une petite fille de village, la plus jolie qu'on eut su voir".to_string()).unwrap().into();
+        let cipher = crate::xor_byte(&plain, 0x5a);
+
+        let (key, found, _) = crate::break_single_byte_with(&cipher, |r| freq_score(r, &FRENCH, 0.16));
+        assert_eq!((key, found), (0x5a, plain));
+    }
+
+    #[test]
+    fn test_quadgram_score() {
+        let english: Raw = Ascii::new("Mention of the weather in this section".to_string()).unwrap().into();
+        let scrambled: Raw = Ascii::new("Tnemnoi fo eht rehtaew ni siht noitces".to_string()).unwrap().into();
+
+        assert!(quadgram_score(&english) < quadgram_score(&scrambled));
+        assert!(quadgram_score(&scrambled) < quadgram_score(&Raw::new(vec![0x00, 0x01, 0x02, 0x03])));
+
+        // empty input is as unlikely as an unknown quadgram, not NaN
+        let empty = quadgram_score(&Raw::new(vec![]));
+        assert!(empty.is_finite());
+        assert_eq!(empty, quadgram_score(&Raw::new(b"ab".to_vec())));
+    }
+
+    #[test]
+    fn test_base64_score() {
+        let base64: Raw = Ascii::new("SSdtIGJhY2sgYW5kIEknbSByaW5naW4nIHRoZSBiZWxs\n".to_string()).unwrap().into();
+        let english: Raw = Ascii::new("I'm back and I'm ringin' the bell".to_string()).unwrap().into();
+
+        assert_eq!(base64_score(&base64), 0.0);
+        assert!(base64_score(&english) > 0.0);
+        assert_eq!(base64_score(&Raw::new(vec![0x00, b'A'])), 0.5);
+    }
+
+    #[test]
+    fn test_base64_score_cracks_xored_base64() {
+        let lyrics = "I'm back and I'm ringin' the bell\nA rockin' on the mike while the fly girls yell\n\
+                      In ecstasy in the back of me\nWell that's my DJ Deshay cuttin' all them Z's";
+        let plaintext = Raw::from(Base64::encode(&Raw::from(lyrics.to_string())).into_string());
+
+        let (key, found, _) = crate::break_single_byte_with(&crate::xor_byte(&plaintext, b'X'), base64_score);
+        assert_eq!((key, found), (b'X', plaintext.clone()));
+
+        let key = Raw::new(b"ICE".to_vec());
+        let (found_key, found) = crate::break_repeating_xor_with(&crate::xor(&plaintext, &key).unwrap(), 3, 3, base64_score).unwrap();
+        assert_eq!((found_key, found), (key, plaintext));
+    }
+
+    #[test]
+    fn test_byte_histogram() {
+        let histogram = Raw::new(b"hello".to_vec()).byte_histogram();
+
+        assert_eq!(histogram[b'l' as usize], 2);
+        assert_eq!(histogram[b'h' as usize], 1);
+        assert_eq!(histogram.iter().sum::<usize>(), 5);
+        assert_eq!(Raw::new(vec![]).byte_histogram(), [0; 256]);
+    }
+
+    #[test]
+    fn test_index_of_coincidence() {
+        let same = Raw::new(vec![0x41; 10]);
+        let distinct = Raw::new((0..=255u8).collect::<Vec<u8>>());
+        let english: Raw = Ascii::new("the quick brown fox jumps over the lazy dog and then the dog sleeps".to_string()).unwrap().into();
+
+        assert_eq!(same.index_of_coincidence(), 1.0);
+        assert_eq!(distinct.index_of_coincidence(), 0.0);
+        assert!(english.index_of_coincidence() > 0.05);
+        assert_eq!(Raw::new(vec![0x41]).index_of_coincidence(), 0.0);
+    }
+
+    #[test]
+    fn test_looks_xor_encrypted() {
+        let plaintext: Raw = Ascii::new("Burning 'em, if you ain't quick and nimble, I go crazy when I hear a cymbal".repeat(3)).unwrap().into();
+        let cipher = crate::xor(&plaintext, &Raw::new(vec![0x8f, 0x13, 0xa7, 0x5c, 0x01])).unwrap();
+
+        // xorshift, near enough to random for this
+        let mut state = 0x2545f491u32;
+        let random = Raw::new((0..4096).map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        }).collect::<Vec<u8>>());
+
+        assert!(looks_xor_encrypted(&cipher));
+        assert!(!looks_xor_encrypted(&plaintext));
+        assert!(!looks_xor_encrypted(&random));
+        assert!(!looks_xor_encrypted(&Raw::new(vec![0x8f; 16])));
+
+        // 6 bits of noise, well under the entropy cutoff and never printable, so it's the flat
+        // column IoC that rules it out
+        let noise = Raw::new(random.iter().map(|b| 0x80 | (b & 0x3f)).collect::<Vec<u8>>());
+        assert!(noise.shannon_entropy() < 7.9);
+        assert_eq!(noise.printable_ratio(), 0.0);
+        assert!(!looks_xor_encrypted(&noise));
+
+        // the documented false positives: long constant runs and structured binary pass the IoC
+        // check whether or not they were xor'd
+        let records = Raw::new((0..64u32).flat_map(|i| i.to_le_bytes()).collect::<Vec<u8>>());
+        assert!(looks_xor_encrypted(&Raw::new(vec![0x8f; 64])));
+        assert!(looks_xor_encrypted(&records));
+        assert!(looks_xor_encrypted(&crate::xor(&records, &Raw::new(vec![0xc3, 0x5a, 0x99])).unwrap()));
+    }
+
+    #[test]
+    fn test_repeated_blocks() {
+        let raw = Raw::new(b"ABCDABCDEFGHABCDxy".to_vec());
+
+        assert_eq!(raw.repeated_blocks(4), 2);
+        assert_eq!(raw.repeated_blocks(8), 0);
+        assert_eq!(raw.repeated_blocks(100), 0);
+        assert_eq!(Raw::new(vec![0x00; 16]).repeated_blocks(1), 15);
+    }
+
+    #[test]
+    fn test_shannon_entropy() {
+        let all_bytes = Raw::new((0..=255u8).collect::<Vec<u8>>());
+        let english: Raw = Ascii::new("the quick brown fox jumps over the lazy dog".to_string()).unwrap().into();
+
+        assert_eq!(all_bytes.shannon_entropy(), 8.0);
+        assert_eq!(Raw::new(vec![0x41; 10]).shannon_entropy(), 0.0);
+        assert_eq!(Raw::new(vec![0x00, 0x01]).shannon_entropy(), 1.0);
+        assert_eq!(Raw::new(vec![]).shannon_entropy(), 0.0);
+
+        let e = english.shannon_entropy();
+        assert!(e > 3.5 && e < 5.0);
+    }
+
+    #[test]
+    fn test_printable_ratio() {
+        assert_eq!(Raw::new(b"ICE ICE\r\n\tbaby".to_vec()).printable_ratio(), 1.0);
+        assert_eq!(Raw::new(vec![b'A', 0x00, 0xff, b' ']).printable_ratio(), 0.5);
+        assert_eq!(Raw::new(vec![0x7f]).printable_ratio(), 0.0);
+        assert_eq!(Raw::new(vec![]).printable_ratio(), 0.0);
+    }
+}
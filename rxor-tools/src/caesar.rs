@@ -0,0 +1,62 @@
+//! Caesar shifts (ROT13 and friends), for quickly ruling out a classical cipher before reaching
+//! for the xor attacks
+
+use crate::Raw;
+
+#[cfg(feature = "std")]
+use crate::english_score;
+
+/// rotate ASCII letters forward by shift places, wrapping within A-Z and a-z. Everything else
+/// is left alone. A shift of 13 is ROT13, and shifting by 26 - n undoes a shift by n
+pub fn caesar_shift(raw: &Raw, shift: u8) -> Raw {
+    let shift = shift % 26;
+
+    raw.into_iter()
+        .map(|b| match b {
+            b'a'..=b'z' => b'a' + (b - b'a' + shift) % 26,
+            b'A'..=b'Z' => b'A' + (b - b'A' + shift) % 26,
+            _ => b,
+        })
+        .collect()
+}
+
+#[cfg(feature = "std")]
+/// break a Caesar shift by trying all 26 and keeping the most English result. Returns the shift
+/// that decrypts (apply it with caesar_shift), the plaintext and its english_score
+pub fn break_caesar(raw: &Raw) -> (u8, Raw, f32) {
+    (0..26u8)
+        .map(|shift| {
+            let candidate = caesar_shift(raw, shift);
+            let score = english_score(&candidate);
+            (shift, candidate, score)
+        })
+        .fold((0, raw.clone(), f32::INFINITY), |best, c| if c.2 < best.2 { c } else { best })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Ascii;
+
+    #[test]
+    fn test_caesar_shift() {
+        let raw: Raw = Ascii::new("Hello, World! xyz".to_string()).unwrap().into();
+
+        assert_eq!(caesar_shift(&raw, 13), Ascii::new("Uryyb, Jbeyq! klm".to_string()).unwrap().into());
+        assert_eq!(caesar_shift(&caesar_shift(&raw, 13), 13), raw);
+        assert_eq!(caesar_shift(&caesar_shift(&raw, 3), 23), raw);
+        assert_eq!(caesar_shift(&raw, 26), raw);
+        assert_eq!(caesar_shift(&Raw::new(vec![0x00, 0xff]), 5), Raw::new(vec![0x00, 0xff]));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_break_caesar() {
+        let plaintext: Raw = Ascii::new("Now that the party is jumping".to_string()).unwrap().into();
+        let cipher = caesar_shift(&plaintext, 7);
+
+        let (shift, recovered, _) = break_caesar(&cipher);
+        assert_eq!(shift, 19);
+        assert_eq!(recovered, plaintext);
+    }
+}
@@ -1,5 +1,10 @@
 //! Tools for handling data encoded with various methods
 
+use alloc::{format, string::String, vec, vec::Vec};
+
+/// base64 chars in value order
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
 /// A string of bytes represented as a hex pair (e.g. 000102FF would be [0x00, 0x01, 0x02, 0xFF] u8s)
 #[derive(Clone, Debug, PartialEq)]
 pub struct Hex(String);
@@ -8,19 +13,132 @@ pub struct Hex(String);
 #[derive(Clone, Debug, PartialEq)]
 pub struct Base64(String);
 
+/// A string of bytes that's been base64 encoded with the URL and filename safe alphabet ('-' and
+/// '_' in place of '+' and '/'), as used by JWTs. Padding is optional
+#[derive(Clone, Debug, PartialEq)]
+pub struct Base64Url(String);
+
+/// A string of bytes that's been base32 encoded (RFC 4648 alphabet, A-Z then 2-7)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Base32(String);
+
+/// A string of bytes written out as bits, most significant first (e.g. "0100100001101001" is "Hi")
+#[derive(Clone, Debug, PartialEq)]
+pub struct Binary(String);
+
 /// A string of ASCII characters
 #[derive(Clone, Debug, PartialEq)]
 pub struct Ascii(String);
 
-/// Raw bytes interface, primary type for decoded interactions
-#[derive(Clone, Debug, PartialEq)]
+/// Raw bytes interface, primary type for decoded interactions. Ordered lexicographically by
+/// bytes, like Vec<u8>
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Raw(Vec<u8>);
 
+/// Text encodings for Raw::encode_as, Raw::decode_from and Raw::decode_auto
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Hex,
+    Base64,
+    Ascii,
+    Base64Url,
+    Base32,
+    Binary,
+}
+
+impl Format {
+    /// every format, in declaration order
+    pub const ALL: [Format; 6] =
+        [Format::Hex, Format::Base64, Format::Ascii, Format::Base64Url, Format::Base32, Format::Binary];
+
+    /// lowercase name of the format, e.g. "base64url"
+    pub fn name(self) -> &'static str {
+        match self {
+            Format::Hex => "hex",
+            Format::Base64 => "base64",
+            Format::Ascii => "ascii",
+            Format::Base64Url => "base64url",
+            Format::Base32 => "base32",
+            Format::Binary => "binary",
+        }
+    }
+
+    /// the format with the given name, None if there isn't one. Inverse of name
+    pub fn from_name(name: &str) -> Option<Format> {
+        Format::ALL.into_iter().find(|f| f.name() == name)
+    }
+}
+
+/// Reasons an encoded string can't be turned into bytes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeErrorKind {
+    /// a char outside the encoding's alphabet
+    InvalidChar(char),
+    /// the string's length doesn't describe a whole number of bytes
+    InvalidLength,
+    /// padding that isn't at the end of the string, or too much of it
+    InvalidPadding,
+}
+
+/// What was wrong with an encoded string, and where. position is the byte offset of the bad
+/// char or the first misplaced/excess '=', and the string's length for InvalidLength. Everything
+/// before the first bad char is ASCII, so it's also the char index
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DecodeError {
+    pub kind: DecodeErrorKind,
+    pub position: usize,
+}
+
+impl DecodeError {
+    pub fn new(kind: DecodeErrorKind, position: usize) -> Self {
+        Self { kind, position }
+    }
+
+    fn invalid_char(position: usize, c: char) -> Self {
+        Self::new(DecodeErrorKind::InvalidChar(c), position)
+    }
+
+    fn invalid_length(len: usize) -> Self {
+        Self::new(DecodeErrorKind::InvalidLength, len)
+    }
+
+    fn invalid_padding(position: usize) -> Self {
+        Self::new(DecodeErrorKind::InvalidPadding, position)
+    }
+}
+
+/// the first char that fails `is`, as a DecodeError at its offset
+fn find_invalid_char(s: &str, is: impl Fn(char) -> bool) -> Result<(), DecodeError> {
+    match s.char_indices().find(|(_, c)| !is(*c)) {
+        Some((i, c)) => Err(DecodeError::invalid_char(i, c)),
+        None => Ok(()),
+    }
+}
+
+impl core::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.kind {
+            DecodeErrorKind::InvalidChar(c) => write!(f, "invalid character {:?} at position {}", c, self.position),
+            DecodeErrorKind::InvalidLength => {
+                write!(f, "input length {} is not a whole number of bytes", self.position)
+            }
+            DecodeErrorKind::InvalidPadding => write!(f, "misplaced or excess padding at position {}", self.position),
+        }
+    }
+}
+
+impl core::error::Error for DecodeError {}
+
 /// trait for explicit decoding (if not using From/Into semantics)
 pub trait Decode {
     fn decode(&self) -> Raw;
 }
 
+/// trait for fallible decoding, reporting why the input couldn't be decoded instead of panicking
+pub trait TryDecode {
+    fn try_decode(&self) -> Result<Raw, DecodeError>;
+}
+
 /// trait for explicit encoding (if not using From/Into)
 pub trait Encode {
     fn encode(this: &Raw) -> Self;
@@ -38,26 +156,47 @@ impl Raw {
         &self.0
     }
 
-    /// determines the bit-level hamming distance between two Raw sequences
+    /// mutable access to bytes, for transforming them in place
+    pub fn get_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.0
+    }
+
+    /// determines the bit-level hamming distance between two Raw sequences. When the lengths
+    /// differ only the overlapping prefix is compared, the extra bytes of the longer one are ignored
     pub fn hamming(&self, other: &Self) -> usize {
-        let mut distance = 0;
+        self.0.iter().zip(other.0.iter()).map(|(l, r)| (l ^ r).count_ones() as usize).sum()
+    }
 
-        for (l, r) in self.0.iter().zip(other.0.iter()) {
-            for b in 0..8 {
-                let bit = 1 << b;
+    /// normalized hamming function (divide through by the number of bits compared), 0.0 if
+    /// either side is empty
+    pub fn hamming_normalized(&self, other: &Self) -> f32 {
+        let compared = self.len().min(other.len());
 
-                if (l & bit) != (r & bit) {
-                    distance += 1;
-                }
-            }
+        if compared == 0 {
+            return 0.0;
         }
 
-        distance
+        self.hamming(other) as f32 / (8.0 * compared as f32)
     }
 
-    /// normalized hamming function (divide through by bits)
-    pub fn hamming_normalized(&self, other: &Self) -> f32 {
-        self.hamming(other) as f32 / (8.0 * self.len() as f32)
+    /// whether the two are the same length and differ in at most max_normalized of their bits
+    /// (0.0 - 1.0, see hamming_normalized). Sequences of different lengths are never similar,
+    /// rather than being judged on their overlap
+    pub fn is_similar(&self, other: &Raw, max_normalized: f32) -> bool {
+        self.len() == other.len() && self.hamming_normalized(other) <= max_normalized
+    }
+
+    /// bit-level hamming distance between the i-th and j-th block_len byte blocks. None if
+    /// block_len is 0 or either block doesn't fit entirely inside the sequence
+    pub fn hamming_blocks(&self, block_len: usize, i: usize, j: usize) -> Option<usize> {
+        if block_len == 0 {
+            return None;
+        }
+
+        let block = |n: usize| self.0.get(n.checked_mul(block_len)?..(n + 1).checked_mul(block_len)?);
+        let (l, r) = (block(i)?, block(j)?);
+
+        Some(l.iter().zip(r).map(|(a, b)| (a ^ b).count_ones() as usize).sum())
     }
 
     /// get iter to u8s
@@ -70,9 +209,242 @@ impl Raw {
         self.0.len()
     }
 
+    /// true if there are no bytes
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// new sequence of self followed by other
+    pub fn concat(&self, other: &Raw) -> Raw {
+        let mut v = self.0.clone();
+        v.extend_from_slice(&other.0);
+
+        Raw(v)
+    }
+
+    /// the byte at i, None if i is past the end. Checked counterpart to raw[i] (get is taken by
+    /// the whole buffer, so slice::get isn't reachable through Deref)
+    pub fn get_byte(&self, i: usize) -> Option<u8> {
+        self.0.get(i).copied()
+    }
+
+    /// read the bytes as a big endian unsigned integer, None if there are more than 8. Fewer
+    /// bytes are the low end of the number, so [0x01, 0x00] is 256
+    pub fn to_u64_be(&self) -> Option<u64> {
+        if self.0.len() > 8 {
+            return None;
+        }
+
+        Some(self.0.iter().fold(0, |n, b| (n << 8) | *b as u64))
+    }
+
+    /// read the bytes as a little endian unsigned integer, None if there are more than 8
+    pub fn to_u64_le(&self) -> Option<u64> {
+        if self.0.len() > 8 {
+            return None;
+        }
+
+        Some(self.0.iter().rev().fold(0, |n, b| (n << 8) | *b as u64))
+    }
+
+    /// the 8 big endian bytes of n
+    pub fn from_u64_be(n: u64) -> Raw {
+        Raw(n.to_be_bytes().to_vec())
+    }
+
+    /// the 8 little endian bytes of n
+    pub fn from_u64_le(n: u64) -> Raw {
+        Raw(n.to_le_bytes().to_vec())
+    }
+
+    /// len pseudo-random bytes from rand's StdRng, always the same for the same seed (and rand
+    /// version) so randomized tests can be replayed. Not suitable for real keys, the seed is the
+    /// whole secret
+    #[cfg(any(test, feature = "rand"))]
+    pub fn random_seeded(len: usize, seed: u64) -> Raw {
+        use rand::{RngCore, SeedableRng};
+
+        let mut r = vec![0; len];
+        rand::rngs::StdRng::seed_from_u64(seed).fill_bytes(&mut r);
+
+        Raw(r)
+    }
+
+    /// len random bytes from rand's thread local generator, different on every call
+    #[cfg(any(test, feature = "rand"))]
+    pub fn random(len: usize) -> Raw {
+        use rand::RngCore;
+
+        let mut r = vec![0; len];
+        rand::thread_rng().fill_bytes(&mut r);
+
+        Raw(r)
+    }
+
+    /// copy out a range of bytes, None if the range isn't inside the sequence
+    pub fn slice(&self, range: core::ops::Range<usize>) -> Option<Raw> {
+        self.0.get(range).map(Raw::from)
+    }
+
+    /// append a byte
+    pub fn push(&mut self, byte: u8) {
+        self.0.push(byte);
+    }
+
+    /// a copy filled out with fill bytes to len, or unchanged if it's already that long or longer
+    pub fn pad_to(&self, len: usize, fill: u8) -> Raw {
+        let mut padded = self.0.clone();
+
+        if padded.len() < len {
+            padded.resize(len, fill);
+        }
+
+        Raw(padded)
+    }
+
+    /// shorten to len bytes, doing nothing if it's already that short, like Vec::truncate
+    pub fn truncate(&mut self, len: usize) {
+        self.0.truncate(len);
+    }
+
+    /// compare the two sequences with any trailing 0x00 bytes trimmed from both, for checking a
+    /// decryption against the expected plaintext when the amount of zero padding isn't known
+    pub fn eq_ignore_trailing_zeros(&self, other: &Raw) -> bool {
+        fn trimmed(bytes: &[u8]) -> &[u8] {
+            let end = bytes.iter().rposition(|b| *b != 0).map_or(0, |i| i + 1);
+            &bytes[..end]
+        }
+
+        trimmed(&self.0) == trimmed(&other.0)
+    }
+
+    /// drop a leading UTF-8 byte order mark (EF BB BF), as Windows editors like to write. Anything
+    /// else is returned as is, so only call this when a BOM isn't meant to be part of the data
+    pub fn strip_bom(&self) -> Raw {
+        Raw(self.0.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(&self.0).to_vec())
+    }
+
+    /// split on every occurrence of delim, which is left out of the pieces. Like str::split,
+    /// adjacent or trailing delimiters give empty pieces and an empty sequence gives one empty
+    /// piece
+    pub fn split_on(&self, delim: u8) -> Vec<Raw> {
+        self.0.split(|b| *b == delim).map(Raw::from).collect()
+    }
+
+    /// split on every non overlapping occurrence of delim, scanning from the start, with the
+    /// same semantics as split_on. An empty delim doesn't split at all
+    pub fn split_on_slice(&self, delim: &[u8]) -> Vec<Raw> {
+        if delim.is_empty() {
+            return vec![self.clone()];
+        }
+
+        let mut pieces = Vec::new();
+        let mut start = 0;
+        let mut i = 0;
+
+        while i + delim.len() <= self.0.len() {
+            if &self.0[i..i + delim.len()] == delim {
+                pieces.push(Raw::from(&self.0[start..i]));
+                i += delim.len();
+                start = i;
+            } else {
+                i += 1;
+            }
+        }
+
+        pieces.push(Raw::from(&self.0[start..]));
+
+        pieces
+    }
+
+    /// iterate over consecutive size byte blocks, the last one may be shorter. Panics if size is
+    /// 0, like slice::chunks
+    pub fn chunks(&self, size: usize) -> impl Iterator<Item = Raw> + '_ {
+        self.0.chunks(size).map(Raw::from)
+    }
+
+    /// decode a hex string in one go, None if it has a non hex char or an odd length
+    pub fn from_hex_str(s: &str) -> Option<Raw> {
+        Raw::decode_from(s, Format::Hex)
+    }
+
+    /// decode a padded base64 string in one go, None if it isn't valid base64
+    pub fn from_base64_str(s: &str) -> Option<Raw> {
+        Raw::decode_from(s, Format::Base64)
+    }
+
+    /// decode a string in the given format, None if it isn't valid for it. As strict as the
+    /// TryFrom<&str> impls, so odd length hex and non ASCII text are rejected
+    pub fn decode_from(s: &str, fmt: Format) -> Option<Raw> {
+        match fmt {
+            Format::Hex => Hex::try_from(s).ok().map(Raw::from),
+            Format::Base64 => Base64::try_from(s).ok().map(Raw::from),
+            Format::Ascii => Ascii::try_from(s).ok().map(Raw::from),
+            Format::Base64Url => Base64Url::try_from(s).ok().map(Raw::from),
+            Format::Base32 => Base32::try_from(s).ok().map(Raw::from),
+            Format::Binary => Binary::try_from(s).ok().map(Raw::from),
+        }
+    }
+
+    /// decode a string whose encoding isn't known, guessing in order: hex (hex chars, even
+    /// length), then padded base64 (a multiple of 4 chars), then plain text. Strings that fit
+    /// more than one, like "deadbeef", go to the first. Text that isn't ASCII is kept as its UTF-8
+    /// bytes, and the empty string is text
+    pub fn decode_auto(s: &str) -> (Raw, Format) {
+        if !s.is_empty() {
+            if let Ok(hex) = Hex::try_from(s) {
+                return (hex.decode(), Format::Hex);
+            }
+
+            if let Ok(b64) = Base64::try_from(s) {
+                if s.len().is_multiple_of(4) {
+                    return (b64.decode(), Format::Base64);
+                }
+            }
+        }
+
+        (Raw(s.as_bytes().to_vec()), Format::Ascii)
+    }
+
+    /// lowercase hex encoding of the bytes
+    pub fn to_hex_string(&self) -> String {
+        self.encode_as(Format::Hex)
+    }
+
+    /// padded base64 encoding of the bytes
+    pub fn to_base64_string(&self) -> String {
+        self.encode_as(Format::Base64)
+    }
+
+    /// encode the bytes in the given format, as that type's Encode does (lowercase hex, padded
+    /// base64, or for Ascii each byte as the char with that value)
+    pub fn encode_as(&self, fmt: Format) -> String {
+        match fmt {
+            Format::Hex => Hex::encode(self).into_string(),
+            Format::Base64 => Base64::encode(self).into_string(),
+            Format::Ascii => Ascii::encode(self).into_string(),
+            Format::Base64Url => Base64Url::encode(self).into_string(),
+            Format::Base32 => Base32::encode(self).into_string(),
+            Format::Binary => Binary::encode(self).into_string(),
+        }
+    }
+
+    /// the shortest block that the sequence is a whole number of repetitions of, e.g. a
+    /// recovered key "ABCABC" collapses to "ABC". Returns the sequence itself if it doesn't repeat
+    pub fn smallest_period(&self) -> Raw {
+        let len = self.0.len();
+
+        let period = (1..=len)
+            .filter(|p| len.is_multiple_of(*p))
+            .find(|p| self.0.iter().enumerate().all(|(i, b)| *b == self.0[i % p]))
+            .unwrap_or(len);
+
+        Raw::new(self.0[..period].to_vec())
+    }
+
 }
 
-impl std::ops::Index<usize> for Raw {
+impl core::ops::Index<usize> for Raw {
     type Output = u8;
 
     fn index(&self, index: usize) -> &Self::Output {
@@ -80,6 +452,21 @@ impl std::ops::Index<usize> for Raw {
     }
 }
 
+impl AsRef<[u8]> for Raw {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// lets a &Raw stand in anywhere a &[u8] is expected
+impl core::ops::Deref for Raw {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
 impl From<Vec<u8>> for Raw {
     fn from(value: Vec<u8>) -> Self {
         Raw(value)
@@ -92,6 +479,31 @@ impl From<&[u8]> for Raw {
     }
 }
 
+impl FromIterator<u8> for Raw {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        Raw(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for Raw {
+    type Item = u8;
+    type IntoIter = alloc::vec::IntoIter<u8>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// iterates bytes by value, unlike iter() which hands out references
+impl<'a> IntoIterator for &'a Raw {
+    type Item = u8;
+    type IntoIter = core::iter::Copied<core::slice::Iter<'a, u8>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter().copied()
+    }
+}
+
 impl Ascii {
     /// if an ASCII string is provided, it will become an ASCII type
     pub fn new(s: String) -> Option<Self> {
@@ -101,6 +513,36 @@ impl Ascii {
             None
         }
     }
+
+    /// borrow the underlying string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// take back ownership of the underlying string
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
+    /// build from bytes that are all ASCII (< 0x80), without going through String::from_utf8
+    pub fn from_bytes(bytes: &[u8]) -> Option<Ascii> {
+        if bytes.is_ascii() {
+            Some(Self(bytes.iter().map(|b| *b as char).collect()))
+        } else {
+            None
+        }
+    }
+
+    /// render bytes for viewing whatever they hold: printable ASCII, newlines and tabs are kept,
+    /// everything else becomes '.'
+    pub fn from_raw_lossy(raw: &Raw) -> String {
+        raw.into_iter()
+            .map(|b| match b {
+                0x20..=0x7e | b'\n' | b'\t' => b as char,
+                _ => '.',
+            })
+            .collect()
+    }
 }
 
 impl Hex {
@@ -113,18 +555,37 @@ impl Hex {
         }
     }
 
+    /// like new, but ignores ASCII whitespace (spaced out bytes, hex dumps split over lines)
+    pub fn new_lenient(s: &str) -> Option<Self> {
+        Hex::new(strip_whitespace(s))
+    }
+
+    /// like new, but also requires an even number of chars so every byte is complete
+    pub fn new_strict(s: String) -> Option<Self> {
+        if Hex::string_is_strict(&s) {
+            Some(Self(s))
+        } else {
+            None
+        }
+    }
+
+    /// borrow the underlying string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// take back ownership of the underlying string
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
     /// check if a char is a hex symbol (0-F)
     pub fn is(c: char) -> bool {
-        match c {
-            'a'..='f' => true,
-            'A'..='F' => true,
-            '0'..='9' => true,
-            _ => false,
-        }
+        c.is_ascii_hexdigit()
     }
 
     /// validate all the chars in a string to be (0-F)
-    pub fn string_is(s: &String) -> bool {
+    pub fn string_is(s: &str) -> bool {
         for c in s.chars() {
             if !Hex::is(c) {
                 return false;
@@ -134,26 +595,111 @@ impl Hex {
         true
     }
 
-    fn to_u8(c: char) -> u8 {
+    /// like string_is, but an odd number of chars is also rejected
+    pub fn string_is_strict(s: &str) -> bool {
+        s.len().is_multiple_of(2) && Hex::string_is(s)
+    }
+
+    /// the value (0-15) of a hex char in either case, None if it isn't a hex char
+    pub fn nibble_value(c: char) -> Option<u8> {
+        Hex::to_u8(c)
+    }
+
+    /// the lowercase hex char for a value, None if it doesn't fit in 4 bits
+    pub fn nibble_char(v: u8) -> Option<char> {
+        if v < 16 {
+            Some(Hex::from_u8(v))
+        } else {
+            None
+        }
+    }
+
+    fn to_u8(c: char) -> Option<u8> {
         match c {
-            'a'..='f' => c as u8 - 'a' as u8 + 10,
-            'A'..='F' => c as u8 - 'A' as u8 + 10,
-            '0'..='9' => c as u8 - '0' as u8,
-            _ => panic!(), /* unreachable! */
+            'a'..='f' => Some(c as u8 - b'a' + 10),
+            'A'..='F' => Some(c as u8 - b'A' + 10),
+            '0'..='9' => Some(c as u8 - b'0'),
+            _ => None,
         }
     }
 
     fn from_u8(u: u8) -> char {
         if u < 10 {
-            (u + '0' as u8) as char
+            (u + b'0') as char
         } else {
-            (u - 10 + 'a' as u8) as char
+            (u - 10 + b'a') as char
+        }
+    }
+
+    /// whether the two hold the same bytes whatever the case of their A-F digits. == compares the
+    /// strings as written, so "DEADBEEF" != "deadbeef"; Hex keeps its case so encode_upper output
+    /// stays uppercase
+    pub fn eq_ignore_case(&self, other: &Hex) -> bool {
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+
+    /// encode with uppercase A-F, where encode (and From<Raw>) produce lowercase
+    pub fn encode_upper(this: &Raw) -> Hex {
+        let mut hex = Hex::encode(this);
+        hex.0.make_ascii_uppercase();
+
+        hex
+    }
+
+    /// encode as lowercase byte pairs joined by sep, e.g. "ab:cd:ef". Returns a String rather
+    /// than Hex since the separator isn't a hex char
+    pub fn encode_with_sep(this: &Raw, sep: &str) -> String {
+        let hex = Hex::encode(this);
+        let pairs: Vec<&str> = hex
+            .0
+            .as_bytes()
+            .chunks(2)
+            .map(|p| core::str::from_utf8(p).expect("hex is ASCII"))
+            .collect();
+
+        pairs.join(sep)
+    }
+
+    /// decode pairs of chars into bytes, with an odd trailing char becoming the high nibble of a
+    /// final byte
+    fn decode_pairs(&self) -> Result<Raw, DecodeError> {
+        let mut v = Vec::with_capacity(self.0.len().div_ceil(2));
+        self.decode_pairs_into(&mut v)?;
+
+        Ok(Raw(v))
+    }
+
+    /// decode_pairs, appending to v
+    fn decode_pairs_into(&self, v: &mut Vec<u8>) -> Result<(), DecodeError> {
+        let mut word = 0;
+
+        for (i, c) in self.0.char_indices() {
+            let bits = Hex::to_u8(c).ok_or(DecodeError::invalid_char(i, c))?;
+
+            if i % 2 == 0 {
+                word = bits << 4;
+            } else {
+                v.push(word | bits);
+            }
+        }
+
+        if self.0.len() % 2 == 1 {
+            v.push(word);
         }
+
+        Ok(())
+    }
+
+    /// decode like Decode::decode, but append the bytes to out instead of allocating a new
+    /// buffer, so one buffer can be reused across many decodes
+    pub fn decode_into(&self, out: &mut Vec<u8>) {
+        self.decode_pairs_into(out).expect("Hex chars are validated on construction")
     }
 }
 
 impl Base64 {
-    /// construct a Base64 representation, if the string is valid Base64 encoding
+    /// construct a Base64 representation, if the string is valid Base64 encoding. Trailing '='
+    /// padding is optional
     pub fn new(s: String) -> Option<Self> {
         if Base64::is_string(&s) {
             Some(Self(s))
@@ -162,6 +708,21 @@ impl Base64 {
         }
     }
 
+    /// like new, but ignores ASCII whitespace (e.g. PEM style line wrapping)
+    pub fn new_lenient(s: &str) -> Option<Self> {
+        Base64::new(strip_whitespace(s))
+    }
+
+    /// borrow the underlying string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// take back ownership of the underlying string
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
     pub fn is(c: char) -> bool {
         match c {
             '0'..='9' => true,
@@ -174,63 +735,407 @@ impl Base64 {
         }
     }
 
-    pub fn is_string(s: &String) -> bool {
-        for c in s.chars() {
-            if !Base64::is(c) {
-                return false;
-            }
-        }
-        true
+    /// validate a whole base64 string: alphabet chars, with at most two '=' that only appear at
+    /// the end of a string that's a multiple of 4 long
+    pub fn is_string(s: &str) -> bool {
+        Base64::validate(s).is_ok()
     }
 
-    fn from_u8(u: u8) -> char {
-        let mut map: Vec<char> = Vec::new();
+    /// is_string with ASCII whitespace ignored, true exactly when new_lenient would accept s
+    pub fn is_string_lenient(s: &str) -> bool {
+        Base64::is_string(&strip_whitespace(s))
+    }
+
+    fn validate(s: &str) -> Result<(), DecodeError> {
+        Base64::validate_with(s, Base64::is)
+    }
 
-        for c in 'A'..='Z' {
-            map.push(c);
+    /// structural checks shared by both base64 alphabets, `is` decides which chars are allowed
+    fn validate_with(s: &str, is: fn(char) -> bool) -> Result<(), DecodeError> {
+        find_invalid_char(s, is)?;
+
+        let data = s.trim_end_matches('=');
+        let padding = s.len() - data.len();
+
+        // '=' anywhere but the tail
+        if let Some(i) = data.find('=') {
+            return Err(DecodeError::invalid_padding(i));
         }
 
-        for c in 'a'..='z' {
-            map.push(c);
+        if padding > 2 {
+            return Err(DecodeError::invalid_padding(data.len() + 2));
         }
 
-        for c in '0'..='9' {
-            map.push(c);
+        if padding > 0 && !s.len().is_multiple_of(4) {
+            return Err(DecodeError::invalid_length(s.len()));
         }
 
-        map.push('+');
-        map.push('/');
+        // a lone trailing sextet doesn't hold enough bits for a byte
+        if data.len() % 4 == 1 {
+            return Err(DecodeError::invalid_length(s.len()));
+        }
 
-        map[u as usize]
+        Ok(())
     }
-    fn to_u8(c: char) -> u8 {
-        match c {
-            'A'..='Z' => c as u8 - 'A' as u8 + 0,
-            'a'..='z' => c as u8 - 'a' as u8 + 26,
-            '0'..='9' => c as u8 - '0' as u8 + 52,
-            '+' => 62,
-            '/' => 63,
-            '=' => 0,
-            _ => panic!(), // unreachable
-        }
+
+    /// the value (0-63) of a base64 char, None for anything outside A-Z, a-z, 0-9, + and /
+    /// (including the '=' padding)
+    pub fn sextet_value(c: char) -> Option<u8> {
+        Base64::to_u8(c)
     }
-}
 
-impl From<String> for Raw {
-    fn from(value: String) -> Self {
-        Raw(value.into_bytes())
+    /// the base64 char for a value, None if it doesn't fit in 6 bits
+    pub fn sextet_char(v: u8) -> Option<char> {
+        BASE64_ALPHABET.get(v as usize).map(|c| *c as char)
     }
-}
 
-// ASCII : RAW conversions
+    fn from_u8(u: u8) -> char {
+        BASE64_ALPHABET[u as usize] as char
+    }
 
-impl Decode for Ascii {
-    fn decode(&self) -> Raw {
+    fn to_u8(c: char) -> Option<u8> {
+        match c {
+            'A'..='Z' => Some(c as u8 - b'A'),
+            'a'..='z' => Some(c as u8 - b'a' + 26),
+            '0'..='9' => Some(c as u8 - b'0' + 52),
+            '+' => Some(62),
+            '/' => Some(63),
+            _ => None,
+        }
+    }
+}
+
+impl Base64Url {
+    /// construct a url safe Base64 representation, if the string is valid with or without padding
+    pub fn new(s: String) -> Option<Self> {
+        if Base64Url::is_string(&s) {
+            Some(Self(s))
+        } else {
+            None
+        }
+    }
+
+    /// borrow the underlying string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// take back ownership of the underlying string
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
+    /// check if a char is in the url safe alphabet (or '=' padding)
+    pub fn is(c: char) -> bool {
+        match c {
+            '-' | '_' => true,
+            '+' | '/' => false,
+            _ => Base64::is(c),
+        }
+    }
+
+    /// validate a whole url safe base64 string, same padding rules as Base64::is_string
+    pub fn is_string(s: &str) -> bool {
+        Base64::validate_with(s, Base64Url::is).is_ok()
+    }
+
+    fn from_u8(u: u8) -> char {
+        match Base64::from_u8(u) {
+            '+' => '-',
+            '/' => '_',
+            c => c,
+        }
+    }
+
+    fn to_u8(c: char) -> Option<u8> {
+        match c {
+            '-' => Some(62),
+            '_' => Some(63),
+            '+' | '/' => None,
+            _ => Base64::to_u8(c),
+        }
+    }
+}
+
+impl Base32 {
+    /// construct a Base32 representation, if the string is valid Base32 encoding
+    pub fn new(s: String) -> Option<Self> {
+        if Base32::is_string(&s) {
+            Some(Self(s))
+        } else {
+            None
+        }
+    }
+
+    /// borrow the underlying string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// take back ownership of the underlying string
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
+    /// check if a char is in the base32 alphabet (or '=' padding)
+    pub fn is(c: char) -> bool {
+        matches!(c, 'A'..='Z' | '2'..='7' | '=')
+    }
+
+    /// validate a whole base32 string: alphabet chars, with padding only at the end of a string
+    /// that's a multiple of 8 long
+    pub fn is_string(s: &str) -> bool {
+        Base32::validate(s).is_ok()
+    }
+
+    fn validate(s: &str) -> Result<(), DecodeError> {
+        find_invalid_char(s, Base32::is)?;
+
+        let data = s.trim_end_matches('=');
+        let padding = s.len() - data.len();
+
+        // '=' anywhere but the tail, or a whole group of it
+        if let Some(i) = data.find('=') {
+            return Err(DecodeError::invalid_padding(i));
+        }
+
+        if padding > 6 {
+            return Err(DecodeError::invalid_padding(data.len() + 6));
+        }
+
+        if padding > 0 && !s.len().is_multiple_of(8) {
+            return Err(DecodeError::invalid_length(s.len()));
+        }
+
+        // a group of 8 chars holds 5 bytes, a partial group can only end after 1-4 whole bytes
+        // (2, 4, 5 or 7 chars)
+        match data.len() % 8 {
+            0 | 2 | 4 | 5 | 7 => Ok(()),
+            _ => Err(DecodeError::invalid_length(s.len())),
+        }
+    }
+
+    fn from_u8(u: u8) -> char {
+        if u < 26 {
+            (u + b'A') as char
+        } else {
+            (u - 26 + b'2') as char
+        }
+    }
+
+    fn to_u8(c: char) -> Option<u8> {
+        match c {
+            'A'..='Z' => Some(c as u8 - b'A'),
+            '2'..='7' => Some(c as u8 - b'2' + 26),
+            _ => None,
+        }
+    }
+}
+
+impl Binary {
+    /// validate input is made of whole bytes of '0'/'1', then consume into Binary
+    pub fn new(s: String) -> Option<Self> {
+        if Binary::string_is(&s) {
+            Some(Self(s))
+        } else {
+            None
+        }
+    }
+
+    /// borrow the underlying string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// take back ownership of the underlying string
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
+    /// check if a char is a bit
+    pub fn is(c: char) -> bool {
+        c == '0' || c == '1'
+    }
+
+    /// validate all the chars in a string are bits, and there are 8 per byte
+    pub fn string_is(s: &str) -> bool {
+        Binary::validate(s).is_ok()
+    }
+
+    fn validate(s: &str) -> Result<(), DecodeError> {
+        find_invalid_char(s, Binary::is)?;
+
+        if !s.len().is_multiple_of(8) {
+            return Err(DecodeError::invalid_length(s.len()));
+        }
+
+        Ok(())
+    }
+}
+
+/// lay out bytes like `hexdump -C`: an offset, 16 bytes in two groups of 8 and an ASCII gutter
+/// with non-printables shown as '.', one line per 16 bytes
+pub fn hexdump(raw: &Raw) -> String {
+    let mut out = String::new();
+
+    for (line, chunk) in raw.0.chunks(16).enumerate() {
+        let (left, right) = chunk.split_at(chunk.len().min(8));
+        let mut hex = Hex::encode_with_sep(&Raw::from(left), " ");
+
+        if !right.is_empty() {
+            hex.push_str("  ");
+            hex.push_str(&Hex::encode_with_sep(&Raw::from(right), " "));
+        }
+
+        let gutter: String = chunk
+            .iter()
+            .map(|b| if (0x20..=0x7e).contains(b) { *b as char } else { '.' })
+            .collect();
+
+        // a full line of hex is 16 pairs, their separators and the gap between the groups
+        out.push_str(&format!("{:08x}  {:<48}  |{}|\n", line * 16, hex, gutter));
+    }
+
+    out
+}
+
+/// copy a string without any of its ASCII whitespace
+fn strip_whitespace(s: &str) -> String {
+    s.chars().filter(|c| !c.is_ascii_whitespace()).collect()
+}
+
+impl core::fmt::Display for Hex {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl core::fmt::Display for Base64 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl core::fmt::Display for Base64Url {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl core::fmt::Display for Base32 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl core::fmt::Display for Binary {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl core::fmt::Display for Ascii {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+// &str : TYPE conversions, stricter than new() and saying what was wrong
+
+impl TryFrom<&str> for Hex {
+    type Error = DecodeError;
+
+    /// unlike new, an odd number of hex chars is rejected
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        find_invalid_char(s, Hex::is)?;
+
+        if s.len() % 2 == 1 {
+            return Err(DecodeError::invalid_length(s.len()));
+        }
+
+        Ok(Self(s.into()))
+    }
+}
+
+impl TryFrom<&str> for Base64 {
+    type Error = DecodeError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Base64::validate(s)?;
+        Ok(Self(s.into()))
+    }
+}
+
+impl TryFrom<&str> for Base64Url {
+    type Error = DecodeError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Base64::validate_with(s, Base64Url::is)?;
+        Ok(Self(s.into()))
+    }
+}
+
+impl TryFrom<&str> for Base32 {
+    type Error = DecodeError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Base32::validate(s)?;
+        Ok(Self(s.into()))
+    }
+}
+
+impl TryFrom<&str> for Binary {
+    type Error = DecodeError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Binary::validate(s)?;
+        Ok(Self(s.into()))
+    }
+}
+
+impl TryFrom<Vec<u8>> for Ascii {
+    type Error = DecodeError;
+
+    /// reports the first non ASCII byte as a char (its Latin-1 reading)
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        match bytes.iter().position(|b| !b.is_ascii()) {
+            Some(i) => Err(DecodeError::invalid_char(i, bytes[i] as char)),
+            None => Ok(Self(String::from_utf8(bytes).expect("ASCII is valid UTF-8"))),
+        }
+    }
+}
+
+impl TryFrom<&str> for Ascii {
+    type Error = DecodeError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        find_invalid_char(s, |c| c.is_ascii())?;
+        Ok(Self(s.into()))
+    }
+}
+
+impl From<String> for Raw {
+    fn from(value: String) -> Self {
+        Raw(value.into_bytes())
+    }
+}
+
+// ASCII : RAW conversions
+
+impl Decode for Ascii {
+    fn decode(&self) -> Raw {
         // we already know that all the chars in c are ASCII types from new()
         Raw(self.0.clone().into_bytes())
     }
 }
 
+impl TryDecode for Ascii {
+    fn try_decode(&self) -> Result<Raw, DecodeError> {
+        find_invalid_char(&self.0, |c| c.is_ascii())?;
+        Ok(self.decode())
+    }
+}
+
 impl Encode for Ascii {
     fn encode(this: &Raw) -> Self {
         let mut s = String::new();
@@ -257,26 +1162,21 @@ impl From<Raw> for Ascii {
 
 // HEX : RAW conversions
 impl Decode for Hex {
+    /// an odd trailing char is decoded as the high nibble of a final byte, see try_decode to
+    /// reject that instead
     fn decode(&self) -> Raw {
-        let mut v = Vec::new();
-
-        let mut word = 0;
-
-        for (i, c) in self.0.chars().enumerate() {
-            let bits = Hex::to_u8(c);
-
-            if i % 2 == 0 {
-                word = bits << 4;
-            } else {
-                v.push(word | bits);
-            }
-        }
+        self.decode_pairs().expect("Hex chars are validated on construction")
+    }
+}
 
+impl TryDecode for Hex {
+    /// decode, rejecting an odd number of hex chars instead of padding out the last nibble
+    fn try_decode(&self) -> Result<Raw, DecodeError> {
         if self.0.len() % 2 == 1 {
-            v.push(word);
+            return Err(DecodeError::invalid_length(self.0.len()));
         }
 
-        Raw(v)
+        self.decode_pairs()
     }
 }
 
@@ -306,25 +1206,94 @@ impl From<Raw> for Hex {
     }
 }
 
+// BINARY : RAW conversions
+impl Decode for Binary {
+    fn decode(&self) -> Raw {
+        self.try_decode().expect("Binary strings are validated on construction")
+    }
+}
+
+impl TryDecode for Binary {
+    fn try_decode(&self) -> Result<Raw, DecodeError> {
+        Binary::validate(&self.0)?;
+
+        let v = self
+            .0
+            .as_bytes()
+            .chunks(8)
+            .map(|byte| byte.iter().fold(0u8, |acc, bit| (acc << 1) | (bit - b'0')))
+            .collect();
+
+        Ok(Raw(v))
+    }
+}
+
+impl Encode for Binary {
+    fn encode(this: &Raw) -> Self {
+        let mut s = String::with_capacity(this.0.len() * 8);
+
+        for b in &this.0 {
+            for i in (0..8).rev() {
+                s.push(if b & (1 << i) != 0 { '1' } else { '0' });
+            }
+        }
+
+        Self(s)
+    }
+}
+
+impl From<Binary> for Raw {
+    fn from(value: Binary) -> Self {
+        value.decode()
+    }
+}
+
+impl From<Raw> for Binary {
+    fn from(value: Raw) -> Self {
+        Binary::encode(&value)
+    }
+}
+
 // Base64 : RAW conversions
 impl Decode for Base64 {
     fn decode(&self) -> Raw {
-        let mut v = Vec::new();
+        self.try_decode().expect("Base64 strings are validated on construction")
+    }
+}
+
+impl TryDecode for Base64 {
+    fn try_decode(&self) -> Result<Raw, DecodeError> {
+        Base64::validate(&self.0)?;
+        Base64::decode_with(&self.0, Base64::to_u8)
+    }
+}
+
+impl Base64 {
+    /// decode a validated base64 string, `to_u8` maps chars of the alphabet in use to sextets
+    fn decode_with(s: &str, to_u8: fn(char) -> Option<u8>) -> Result<Raw, DecodeError> {
+        let mut v = Vec::with_capacity(s.len() / 4 * 3 + 2);
+        Base64::decode_with_into(s, to_u8, &mut v)?;
+
+        Ok(Raw(v))
+    }
+
+    /// decode_with, appending to v
+    fn decode_with_into(s: &str, to_u8: fn(char) -> Option<u8>, v: &mut Vec<u8>) -> Result<(), DecodeError> {
+        let start = v.len();
 
-        // need to combine groups of 6 into groups of 8
-        //let padding_bits = 2 * self.0.chars().filter(|c| *c == '=').count();
+        // need to combine groups of 6 into groups of 8. Each '=' stands in for a sextet that
+        // was never encoded, so only the data sextets contribute bits
+        let data = s.trim_end_matches('=');
+
+        // whatever bits are left over once the last full byte is out are padding, not data
+        let byte_len = data.len() * 6 / 8;
 
         // take it in 6 bits at a time
         let mut bit_index = 0;
         let mut working_byte = 0;
 
-        for b64 in self.0.chars() {
-            // we've reached the end
-            if b64 == '=' {
-                break;
-            }
-
-            let six_bits = Base64::to_u8(b64);
+        for (i, b64) in data.char_indices() {
+            let six_bits = to_u8(b64).ok_or(DecodeError::invalid_char(i, b64))?;
 
             // bit index = where we "left off" in the previous
             // byte.
@@ -360,7 +1329,7 @@ impl Decode for Base64 {
                     working_byte = (six_bits & 0b00_00_11) << 6;
                 }
                 2 => {
-                    working_byte |= (six_bits & 0b11_11_11) << 0;
+                    working_byte |= six_bits & 0b11_11_11;
                     v.push(working_byte);
                 }
 
@@ -370,43 +1339,139 @@ impl Decode for Base64 {
             bit_index = (bit_index + 6) % 8;
         }
 
-        //working_byte <<= padding_bits;
-        // if padding_bits {
-        //     v.push(working_byte >> padding_bits);
-        // }
+        v.truncate(start + byte_len);
 
-        Raw(v)
+        Ok(())
+    }
+
+    /// decode like Decode::decode, but append the bytes to out instead of allocating a new
+    /// buffer, so one buffer can be reused across many decodes
+    pub fn decode_into(&self, out: &mut Vec<u8>) {
+        Base64::decode_with_into(&self.0, Base64::to_u8, out).expect("Base64 strings are validated on construction")
     }
 }
 
-impl Encode for Base64 {
-    fn encode(this: &Raw) -> Self {
-        // take 8 bit words and generate 6 bit phrases
-        let mut s = String::new();
-        let mut bit_index: u128 = 0;
-        let mut buffer = 0;
+/// Decodes base64 that arrives in pieces (e.g. lines from a BufReader), carrying partial
+/// sextet groups over between calls to push. Accepts what Base64::new_lenient does: whitespace
+/// anywhere is skipped and '=' padding is optional
+#[derive(Clone, Debug, Default)]
+pub struct Base64Decoder {
+    out: Vec<u8>,
+    /// bits that don't make up a whole byte yet
+    buffer: u16,
+    /// how many bits are in buffer, always under 8 between chars
+    bits: u32,
+    /// data chars and '=' seen so far, for the length checks in finish
+    data: usize,
+    padding: usize,
+    /// offset of the first '=', and of the next char to be pushed, across every push so far
+    padding_at: usize,
+    consumed: usize,
+}
 
-        for byte in &this.0 {
-            // Cases:
-            //    | _ _ _ _ _ _ | _ _ _ _ _ _ | ...
-            // 0>   x x x x x x   x x _ _ _ _
-            //    | _ _ _ _ _ _ | _ _ _ _ _ _ | ...
-            // 2>   - - x x x x   x x x x _ _
-            //    | _ _ _ _ _ _ | _ _ _ _ _ _ | ...
-            // 4>   - - - - x x   x x x x x x
-            // <and wraps>
-            match bit_index % 6 {
-                0 => {
-                    s.push(Base64::from_u8(byte >> 2));
-                    buffer = (byte & 0b11) << 4;
+impl Base64Decoder {
+    /// a decoder that hasn't seen any input
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// decode the next piece of input. Errors on a char outside the alphabet or data after
+    /// padding; the decoder shouldn't be used after an error
+    pub fn push(&mut self, chunk: &str) -> Result<(), DecodeError> {
+        for (i, c) in chunk.char_indices() {
+            let at = self.consumed + i;
+
+            if c.is_ascii_whitespace() {
+                continue;
+            }
+
+            if c == '=' {
+                if self.padding == 0 {
+                    self.padding_at = at;
                 }
-                2 => {
-                    s.push(Base64::from_u8(buffer | (byte >> 4)));
-                    buffer = (byte & 0b1111) << 2;
+
+                self.padding += 1;
+
+                if self.padding > 2 {
+                    return Err(DecodeError::invalid_padding(at));
+                }
+
+                continue;
+            }
+
+            let six_bits = Base64::to_u8(c).ok_or(DecodeError::invalid_char(at, c))?;
+
+            if self.padding > 0 {
+                return Err(DecodeError::invalid_padding(self.padding_at));
+            }
+
+            self.data += 1;
+            self.buffer = (self.buffer << 6) | six_bits as u16;
+            self.bits += 6;
+
+            if self.bits >= 8 {
+                self.bits -= 8;
+                self.out.push((self.buffer >> self.bits) as u8);
+                self.buffer &= (1 << self.bits) - 1;
+            }
+        }
+
+        self.consumed += chunk.len();
+
+        Ok(())
+    }
+
+    /// check the input ended on a whole number of bytes and return them. Leftover bits in a
+    /// partial last group are dropped, as Base64's decode does
+    pub fn finish(self) -> Result<Raw, DecodeError> {
+        if self.padding > 0 && !(self.data + self.padding).is_multiple_of(4) {
+            return Err(DecodeError::invalid_length(self.consumed));
+        }
+
+        if self.data % 4 == 1 {
+            return Err(DecodeError::invalid_length(self.consumed));
+        }
+
+        Ok(Raw(self.out))
+    }
+}
+
+impl Encode for Base64 {
+    fn encode(this: &Raw) -> Self {
+        Self(Base64::encode_with(this, Base64::from_u8, true))
+    }
+}
+
+impl Base64 {
+    /// encode bytes using the alphabet described by `from_u8`, optionally '=' padding the end out
+    /// to a multiple of 4 chars
+    fn encode_with(this: &Raw, from_u8: fn(u8) -> char, pad: bool) -> String {
+        // take 8 bit words and generate 6 bit phrases
+        let mut s = String::new();
+        let mut bit_index: u128 = 0;
+        let mut buffer = 0;
+
+        for byte in &this.0 {
+            // Cases:
+            //    | _ _ _ _ _ _ | _ _ _ _ _ _ | ...
+            // 0>   x x x x x x   x x _ _ _ _
+            //    | _ _ _ _ _ _ | _ _ _ _ _ _ | ...
+            // 2>   - - x x x x   x x x x _ _
+            //    | _ _ _ _ _ _ | _ _ _ _ _ _ | ...
+            // 4>   - - - - x x   x x x x x x
+            // <and wraps>
+            match bit_index % 6 {
+                0 => {
+                    s.push(from_u8(byte >> 2));
+                    buffer = (byte & 0b11) << 4;
+                }
+                2 => {
+                    s.push(from_u8(buffer | (byte >> 4)));
+                    buffer = (byte & 0b1111) << 2;
                 }
                 4 => {
-                    s.push(Base64::from_u8(buffer | (byte >> 6)));
-                    s.push(Base64::from_u8(byte & 0b111111));
+                    s.push(from_u8(buffer | (byte >> 6)));
+                    s.push(from_u8(byte & 0b111111));
                 }
                 _ => panic!(), // unreachable
             }
@@ -415,18 +1480,40 @@ impl Encode for Base64 {
         }
 
         let starting_bits = this.0.len() * 8;
-        let total_b64 = (starting_bits + 5) / 6;
+        let total_b64 = starting_bits.div_ceil(6);
         let padding_bits = total_b64 * 6 - starting_bits;
 
         if padding_bits != 0 {
-            s.push(Base64::from_u8(buffer));
+            s.push(from_u8(buffer));
 
+            // a 1 byte tail leaves 4 padding bits (2 chars short of a group of 4), a 2 byte tail
+            // leaves 2 (1 char short), so each '=' stands for 2 bits
             for _ in 0..(padding_bits / 2) {
-                s.push('=');
+                if pad {
+                    s.push('=');
+                }
             }
         }
 
-        Self(s)
+        s
+    }
+}
+
+impl Base64 {
+    /// encode with padding, broken into lines of width chars (64 for PEM, 76 for MIME) joined by
+    /// '\n', with no newline after the last. new_lenient reads it back. Panics if width is 0
+    pub fn encode_wrapped(this: &Raw, width: usize) -> String {
+        assert!(width > 0, "width must be at least 1");
+
+        let b64 = Base64::encode(this);
+        let lines: Vec<&str> = b64
+            .0
+            .as_bytes()
+            .chunks(width)
+            .map(|l| core::str::from_utf8(l).expect("base64 is ASCII"))
+            .collect();
+
+        lines.join("\n")
     }
 }
 
@@ -442,8 +1529,122 @@ impl From<Base64> for Raw {
     }
 }
 
+// Base64Url : RAW conversions
+impl Decode for Base64Url {
+    fn decode(&self) -> Raw {
+        self.try_decode().expect("Base64Url strings are validated on construction")
+    }
+}
+
+impl TryDecode for Base64Url {
+    fn try_decode(&self) -> Result<Raw, DecodeError> {
+        Base64::validate_with(&self.0, Base64Url::is)?;
+        Base64::decode_with(&self.0, Base64Url::to_u8)
+    }
+}
+
+impl Encode for Base64Url {
+    /// encodes without padding, the way JWTs expect
+    fn encode(this: &Raw) -> Self {
+        Self(Base64::encode_with(this, Base64Url::from_u8, false))
+    }
+}
+
+impl From<Raw> for Base64Url {
+    fn from(value: Raw) -> Self {
+        Base64Url::encode(&value)
+    }
+}
+
+impl From<Base64Url> for Raw {
+    fn from(value: Base64Url) -> Self {
+        value.decode()
+    }
+}
+
+// Base32 : RAW conversions
+impl Decode for Base32 {
+    fn decode(&self) -> Raw {
+        self.try_decode().expect("Base32 strings are validated on construction")
+    }
+}
+
+impl TryDecode for Base32 {
+    fn try_decode(&self) -> Result<Raw, DecodeError> {
+        Base32::validate(&self.0)?;
+
+        let mut v = Vec::new();
+
+        // shift 5 bits at a time into the buffer, and take a byte off the top whenever there's
+        // at least 8 of them. Leftover bits at the end are padding
+        let mut buffer: u16 = 0;
+        let mut bits = 0;
+
+        for (i, c) in self.0.trim_end_matches('=').char_indices() {
+            let five_bits = Base32::to_u8(c).ok_or(DecodeError::invalid_char(i, c))?;
+
+            buffer = (buffer << 5) | five_bits as u16;
+            bits += 5;
+
+            if bits >= 8 {
+                bits -= 8;
+                v.push((buffer >> bits) as u8);
+                buffer &= (1 << bits) - 1;
+            }
+        }
+
+        Ok(Raw(v))
+    }
+}
+
+impl Encode for Base32 {
+    fn encode(this: &Raw) -> Self {
+        let mut s = String::new();
+
+        let mut buffer: u16 = 0;
+        let mut bits = 0;
+
+        for byte in &this.0 {
+            buffer = (buffer << 8) | *byte as u16;
+            bits += 8;
+
+            while bits >= 5 {
+                bits -= 5;
+                s.push(Base32::from_u8((buffer >> bits) as u8 & 0b11111));
+            }
+
+            buffer &= (1 << bits) - 1;
+        }
+
+        // zero fill the last partial char, then pad out to a whole group of 8
+        if bits > 0 {
+            s.push(Base32::from_u8((buffer << (5 - bits)) as u8 & 0b11111));
+        }
+
+        while !s.len().is_multiple_of(8) {
+            s.push('=');
+        }
+
+        Self(s)
+    }
+}
+
+impl From<Raw> for Base32 {
+    fn from(value: Raw) -> Self {
+        Base32::encode(&value)
+    }
+}
+
+impl From<Base32> for Raw {
+    fn from(value: Base32) -> Self {
+        value.decode()
+    }
+}
+
+#[cfg(test)]
 mod test {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_all() {
@@ -511,6 +1712,816 @@ mod test {
         let rhs = Ascii::new("wokka wokka!!!".to_string()).unwrap();
 
         assert_eq!(lhs.decode().hamming(&rhs.decode()), 37);
+    }
+
+    #[test]
+    fn test_hamming_every_byte_pair() {
+        // against counting differing bits one at a time
+        for l in 0..=255u8 {
+            for r in 0..=255u8 {
+                let expected = (0..8).filter(|b| (l >> b) & 1 != (r >> b) & 1).count();
+                assert_eq!(Raw::new(vec![l]).hamming(&Raw::new(vec![r])), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_into() {
+        let mut out = Vec::new();
+
+        for line in ["494345", "0a0b", "abc"] {
+            let hex = Hex::new(line.to_string()).unwrap();
+
+            out.clear();
+            hex.decode_into(&mut out);
+            assert_eq!(Raw::new(out.clone()), hex.decode());
+        }
+
+        // appends after whatever is already there
+        let mut out = vec![0xff];
+        Base64::new("SUNFIQ==".to_string()).unwrap().decode_into(&mut out);
+        Base64::new("SUM".to_string()).unwrap().decode_into(&mut out);
+        assert_eq!(out, b"\xffICE!IC".to_vec());
+    }
+
+    #[test]
+    fn test_is_similar() {
+        let block = Raw::new(b"YELLOW SUBMARINE".to_vec());
+        let mut nearly = block.clone();
+        nearly.get_mut()[0] ^= 0b11;
+
+        // 2 of 128 bits differ
+        assert!(block.is_similar(&nearly, 0.02));
+        assert!(!block.is_similar(&nearly, 0.01));
+        assert!(block.is_similar(&block, 0.0));
+
+        // the shared prefix matches, but the lengths don't
+        assert!(!block.is_similar(&block.slice(0..8).unwrap(), 1.0));
+        assert!(Raw::new(vec![]).is_similar(&Raw::new(vec![]), 0.0));
+    }
+
+    #[test]
+    fn test_hamming_unequal_lengths() {
+        let short = Raw::new(vec![0x00, 0xff, 0x0f]);
+        let long = Raw::new(vec![0xff, 0xff, 0x00, 0xff, 0xff]);
+
+        // only the first 3 bytes are compared, from either side
+        assert_eq!(short.hamming(&long), 12);
+        assert_eq!(long.hamming(&short), 12);
+        assert_eq!(short.hamming_normalized(&long), 0.5);
+        assert_eq!(long.hamming_normalized(&short), 0.5);
+
+        assert_eq!(short.hamming_normalized(&Raw::new(vec![])), 0.0);
+
+    }
+
+    #[test]
+    fn test_base64_padding() {
+        let decode = |s: &str| Base64::new(s.to_string()).unwrap().decode();
+
+        assert_eq!(decode("TWFu"), Raw::new(b"Man".to_vec()));
+        assert_eq!(decode("TWE="), Raw::new(b"Ma".to_vec()));
+        assert_eq!(decode("TQ=="), Raw::new(b"M".to_vec()));
+        assert_eq!(decode("TWFuTQ=="), Raw::new(b"ManM".to_vec()));
+
+        // padding bits must not leak into the output, even with every bit set
+        for bytes in [vec![0xff], vec![0xff, 0xfe], vec![0xff, 0xfe, 0xfd], vec![0xff, 0xfe, 0xfd, 0xfc]] {
+            let raw = Raw::new(bytes);
+            let b64: Base64 = raw.clone().into();
+
+            assert_eq!(b64.decode(), raw);
+        }
+    }
+
+    #[test]
+    fn test_base64_encode_padding() {
+        // reference outputs from RFC 4648 implementations
+        assert_eq!(Base64::encode(&Raw::new(vec![0x00])).as_str(), "AA==");
+        assert_eq!(Base64::encode(&Raw::new(vec![0x00, 0x01])).as_str(), "AAE=");
+        assert_eq!(Base64::encode(&Raw::new(vec![0x00, 0x01, 0x02])).as_str(), "AAEC");
+        assert_eq!(Base64::encode(&Raw::new(vec![0x00, 0x01, 0x02, 0x03])).as_str(), "AAECAw==");
+        assert_eq!(Base64::encode(&Raw::new(vec![])).as_str(), "");
+    }
+
+    #[test]
+    fn test_base64_structure() {
+        let valid = |s: &str| Base64::new(s.to_string()).is_some();
+
+        assert!(valid(""));
+        assert!(valid("TWFu"));
+        assert!(valid("TWE="));
+        assert!(valid("TQ=="));
+
+        assert!(!valid("A="));
+        assert!(!valid("==AB"));
+        assert!(!valid("TW=u"));
+        assert!(!valid("TWFu=A=="));
+        assert!(!valid("T==="));
+        assert!(!valid("TWFuT"));
+    }
+
+    #[test]
+    fn test_base64_unpadded() {
+        for (unpadded, padded, plain) in [("SGVsbG8", "SGVsbG8=", "Hello"), ("SGVsbA", "SGVsbA==", "Hell"), ("SGk", "SGk=", "Hi")] {
+            let b64 = Base64::new(unpadded.to_string()).unwrap();
+            let expected = Raw::new(plain.as_bytes().to_vec());
+
+            // the final partial byte comes from the input length, not the padding
+            assert_eq!(b64.decode(), expected);
+            assert_eq!(b64.try_decode(), Ok(expected.clone()));
+            assert_eq!(Base64::new(padded.to_string()).unwrap().decode(), expected);
+        }
+    }
+
+    #[test]
+    fn test_base64_encode_wrapped() {
+        let raw = Raw::new(b"I'm killing your brain like a poisonous mushroom".to_vec());
+
+        let wrapped = Base64::encode_wrapped(&raw, 28);
+        assert_eq!(wrapped, "SSdtIGtpbGxpbmcgeW91ciBicmFp\nbiBsaWtlIGEgcG9pc29ub3VzIG11\nc2hyb29t");
+        assert_eq!(Base64::new_lenient(&wrapped).unwrap().decode(), raw);
+
+        // a whole number of lines doesn't leave an empty one at the end
+        assert_eq!(Base64::encode_wrapped(&Raw::new(b"ICE".to_vec()), 4), "SUNF");
+        assert!(Base64::encode_wrapped(&raw, 76).lines().all(|l| l.len() <= 76));
+        assert_eq!(Base64::encode_wrapped(&Raw::new(vec![]), 64), "");
+    }
+
+    #[test]
+    fn test_hex_strict() {
+        assert!(Hex::string_is("abc"));
+        assert!(!Hex::string_is_strict("abc"));
+        assert!(Hex::string_is_strict("abcd"));
+        assert!(Hex::string_is_strict(""));
+        assert!(!Hex::string_is_strict("zz"));
+
+        assert!(Hex::new("abc".to_string()).is_some());
+        assert_eq!(Hex::new_strict("abc".to_string()), None);
+        assert_eq!(Hex::new_strict("abcd".to_string()).unwrap().decode(), Raw::new(vec![0xab, 0xcd]));
+    }
+
+    #[test]
+    fn test_hex_odd_length() {
+        let hex = Hex::new("abc".to_string()).unwrap();
+        assert_eq!(hex.try_decode(), Err(DecodeError::new(DecodeErrorKind::InvalidLength, 3)));
+
+        let hex = Hex::new("abcd".to_string()).unwrap();
+        assert_eq!(hex.try_decode(), Ok(Raw::new(vec![0xab, 0xcd])));
+    }
+
+    #[test]
+    fn test_try_decode() {
+        // build the types directly, skipping the validation in new()
+        assert_eq!(Hex("0g".to_string()).try_decode(), Err(DecodeError::new(DecodeErrorKind::InvalidChar('g'), 1)));
+        assert_eq!(Base64("TW-u".to_string()).try_decode(), Err(DecodeError::new(DecodeErrorKind::InvalidChar('-'), 2)));
+        assert_eq!(Base64("TW=u".to_string()).try_decode(), Err(DecodeError::new(DecodeErrorKind::InvalidPadding, 2)));
+        assert_eq!(Base64("TWFuT".to_string()).try_decode(), Err(DecodeError::new(DecodeErrorKind::InvalidLength, 5)));
+        assert_eq!(Ascii("caf\u{e9}".to_string()).try_decode(), Err(DecodeError::new(DecodeErrorKind::InvalidChar('\u{e9}'), 3)));
+
+        assert_eq!(
+            Base64::try_from("SSdt\nIGtp").unwrap_err().to_string(),
+            "invalid character '\\n' at position 4"
+        );
+
+        assert_eq!(Base64("TWE=".to_string()).try_decode(), Ok(Raw::new(b"Ma".to_vec())));
+        assert_eq!(Ascii("Ma".to_string()).try_decode(), Ok(Raw::new(b"Ma".to_vec())));
+    }
+
+    #[test]
+    fn test_display() {
+        let raw = Raw::new(b"Hi!".to_vec());
+
+        assert_eq!(Hex::from(raw.clone()).to_string(), "486921");
+        assert_eq!(Base64::from(raw.clone()).to_string(), "SGkh");
+        assert_eq!(format!("{}", Ascii::from(raw)), "Hi!");
+    }
+
+    #[test]
+    fn test_string_accessors() {
+        // casing survives, unlike a trip through Raw
+        let hex = Hex::new("DeadBeef".to_string()).unwrap();
+        assert_eq!(hex.as_str(), "DeadBeef");
+        assert_eq!(Hex::from(hex.decode()).as_str(), "deadbeef");
+        assert_eq!(hex.into_string(), "DeadBeef".to_string());
+
+        let b64 = Base64::new("SGkh".to_string()).unwrap();
+        assert_eq!(b64.as_str(), "SGkh");
+        assert_eq!(b64.into_string(), "SGkh".to_string());
+
+        let ascii = Ascii::new("Hi!".to_string()).unwrap();
+        assert_eq!(ascii.as_str(), "Hi!");
+        assert_eq!(ascii.into_string(), "Hi!".to_string());
+    }
+
+    #[test]
+    fn test_base64_url() {
+        // JWT header and payload segments
+        let header = Base64Url::new("eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9".to_string()).unwrap();
+        assert_eq!(header.decode(), Raw::new(br#"{"alg":"HS256","typ":"JWT"}"#.to_vec()));
+        assert_eq!(Base64Url::from(header.decode()), header);
+
+        let payload = Base64Url::new("eyJzdWIiOiIxMjM0NTY3ODkwIiwibmFtZSI6IkpvaG4gRG9lIiwiaWF0IjoxNTE2MjM5MDIyfQ".to_string()).unwrap();
+        let expected = br#"{"sub":"1234567890","name":"John Doe","iat":1516239022}"#;
+        assert_eq!(payload.decode(), Raw::new(expected.to_vec()));
+        assert_eq!(Base64Url::from(payload.decode()), payload);
+
+        // the two alphabets only differ in the last two sextets
+        let raw = Raw::new(vec![0xfb, 0xff]);
+        assert_eq!(Base64::from(raw.clone()).as_str(), "+/8=");
+        assert_eq!(Base64Url::from(raw.clone()).as_str(), "-_8");
+        assert_eq!(Base64Url::new("-_8=".to_string()).unwrap().decode(), raw);
+
+        assert_eq!(Base64Url::new("+/8".to_string()), None);
+        assert_eq!(Base64::new("-_8=".to_string()), None);
+    }
+
+    #[test]
+    fn test_base32() {
+        // RFC 4648 test vectors, covering every remainder mod 5
+        let vectors = [
+            ("", ""),
+            ("f", "MY======"),
+            ("fo", "MZXQ===="),
+            ("foo", "MZXW6==="),
+            ("foob", "MZXW6YQ="),
+            ("fooba", "MZXW6YTB"),
+            ("foobar", "MZXW6YTBOI======"),
+        ];
+
+        for (ascii, b32) in vectors {
+            let raw = Raw::new(ascii.as_bytes().to_vec());
+            let expected = Base32::new(b32.to_string()).unwrap();
+
+            assert_eq!(Base32::from(raw.clone()), expected);
+            assert_eq!(expected.decode(), raw);
+        }
+
+        // TOTP secrets usually come unpadded
+        let secret = Base32::new("JBSWY3DPEHPK3PXP".to_string()).unwrap();
+        assert_eq!(secret.decode(), Raw::new(b"Hello!\xde\xad\xbe\xef".to_vec()));
+        assert_eq!(Base32::from(secret.decode()), secret);
+
+        let secret = Base32::new("GEZDGNBVGY3TQOJQ".to_string()).unwrap();
+        assert_eq!(secret.decode(), Raw::new(b"1234567890".to_vec()));
+
+        assert_eq!(Base32::new("MZXW6YTB1".to_string()), None);
+        assert_eq!(Base32::new("MY=====".to_string()), None);
+        assert_eq!(Base32::new("M=Y=====".to_string()), None);
+        assert_eq!(Base32::new("MZX".to_string()), None);
+    }
+
+    #[test]
+    fn test_binary() {
+        let hi = Binary::new("0100100001101001".to_string()).unwrap();
+        assert_eq!(hi.decode(), Raw::new(b"Hi".to_vec()));
+        assert_eq!(Binary::from(hi.decode()), hi);
+
+        let raw = Raw::new(vec![0x00, 0x01, 0x80, 0xff]);
+        assert_eq!(Binary::from(raw.clone()).as_str(), "00000000000000011000000011111111");
+        assert_eq!(Binary::from(raw.clone()).decode(), raw);
+
+        assert_eq!(Binary::new("0100100".to_string()), None);
+        assert_eq!(Binary::new("01001002".to_string()), None);
+    }
+
+    #[test]
+    fn test_hex_encode_upper() {
+        let raw = Raw::new(vec![0xab, 0xcd, 0x01]);
+
+        assert_eq!(Hex::encode_upper(&raw).as_str(), "ABCD01");
+        assert_eq!(Hex::encode(&raw).as_str(), "abcd01");
+        assert_eq!(Hex::encode_upper(&raw).decode(), raw);
+    }
+
+    #[test]
+    fn test_hex_encode_with_sep() {
+        let raw = Raw::new(vec![0xab, 0xcd, 0x01]);
+
+        assert_eq!(Hex::encode_with_sep(&raw, " "), "ab cd 01");
+        assert_eq!(Hex::encode_with_sep(&raw, ":"), "ab:cd:01");
+        assert_eq!(Hex::encode_with_sep(&raw, ""), "abcd01");
+        assert_eq!(Hex::encode_with_sep(&Raw::new(vec![0x01]), ":"), "01");
+        assert_eq!(Hex::encode_with_sep(&Raw::new(vec![]), ":"), "");
+
+        // spaces are the separator new_lenient can read back
+        assert_eq!(Hex::new_lenient(&Hex::encode_with_sep(&raw, " ")).unwrap().decode(), raw);
+    }
+
+    #[test]
+    fn test_hexdump() {
+        let raw = Raw::new(b"Cooking MC's like a pound\x00\xff".to_vec());
+
+        assert_eq!(
+            hexdump(&raw),
+            "00000000  43 6f 6f 6b 69 6e 67 20  4d 43 27 73 20 6c 69 6b  |Cooking MC's lik|\n\
+             00000010  65 20 61 20 70 6f 75 6e  64 00 ff                 |e a pound..|\n"
+        );
+
+        assert_eq!(hexdump(&Raw::new(vec![0x41])), format!("00000000  41{}  |A|\n", " ".repeat(46)));
+        assert_eq!(hexdump(&Raw::new(vec![])), "");
+    }
+
+    #[test]
+    fn test_new_lenient() {
+        let hex = Hex::new_lenient("de ad\nbe\tef\r\n").unwrap();
+        assert_eq!(hex.as_str(), "deadbeef");
+        assert_eq!(Hex::new("de ad".to_string()), None);
+        assert_eq!(Hex::new_lenient("de ag"), None);
+
+        let b64 = Base64::new_lenient("SSdtIGtpbGxpbmcgeW91ciBicmFp\nbiBsaWtlIGEgcG9pc29ub3VzIG11\nc2hyb29t\n").unwrap();
+        assert_eq!(b64.decode(), Raw::new(b"I'm killing your brain like a poisonous mushroom".to_vec()));
+        assert_eq!(Base64::new("SGkh\n".to_string()), None);
+
+        // whitespace doesn't excuse structural problems
+        assert_eq!(Base64::new_lenient("TW=u\n"), None);
+    }
+
+    #[test]
+    fn test_base64_is_string_structure() {
+        let wrapped = "SSdtIGtpbGxpbmcgeW91ciBicmFp\r\nbiBsaWtlIGEgcG9pc29ub3VzIG11\r\nc2hyb29tIQ==\r\n";
+
+        assert!(!Base64::is_string(wrapped));
+        assert!(Base64::is_string_lenient(wrapped));
+        assert!(Base64::is_string_lenient("SGk=\n"));
+
+        // '=' only at the end, at most two of them, and only to fill out the last group of 4
+        for bad in ["TW=u", "SGk=SGk=", "S===", "SGkh\nS", "SGk==="] {
+            assert!(!Base64::is_string_lenient(bad), "{bad:?}");
+        }
+
+        assert!(!Base64::is_string("TWFu="));
+        assert!(Base64::is_string("TWE"));
+        assert!(Base64::is_string("TWE="));
+    }
+
+    #[test]
+    fn test_raw_as_slice() {
+        fn sum(bytes: &[u8]) -> u32 {
+            bytes.iter().map(|b| *b as u32).sum()
+        }
+
+        fn first<T: AsRef<[u8]>>(bytes: T) -> Option<u8> {
+            bytes.as_ref().first().cloned()
+        }
+
+        let raw = Raw::new(vec![0x01, 0x02, 0x03]);
+
+        assert_eq!(sum(&raw), 6);
+        assert_eq!(first(&raw), Some(0x01));
+        assert!(raw.ends_with(&[0x02, 0x03]));
+    }
+
+    #[test]
+    fn test_raw_get_byte() {
+        let raw = Raw::new(vec![0x01, 0x02]);
+
+        assert_eq!(raw.get_byte(0), Some(0x01));
+        assert_eq!(raw.get_byte(1), Some(0x02));
+        assert_eq!(raw.get_byte(2), None);
+        assert_eq!(Raw::new(vec![]).get_byte(0), None);
+    }
+
+    #[test]
+    fn test_raw_u64() {
+        let raw = Raw::new(vec![0x01, 0x00]);
+
+        assert_eq!(raw.to_u64_be(), Some(0x0100));
+        assert_eq!(raw.to_u64_le(), Some(0x0001));
+        assert_eq!(Raw::new(vec![]).to_u64_be(), Some(0));
+        assert_eq!(Raw::new(vec![0xff; 9]).to_u64_be(), None);
+        assert_eq!(Raw::new(vec![0xff; 9]).to_u64_le(), None);
+
+        let n = 0x0123_4567_89ab_cdef;
+        assert_eq!(Raw::from_u64_be(n), Raw::new(vec![0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef]));
+        assert_eq!(Raw::from_u64_be(n).to_u64_be(), Some(n));
+        assert_eq!(Raw::from_u64_le(n).to_u64_le(), Some(n));
+        assert_eq!(Raw::from_u64_le(n).to_u64_be(), Some(n.swap_bytes()));
+    }
+
+    #[test]
+    fn test_raw_ord_hash() {
+        let candidates = [
+            Raw::new(b"ICE".to_vec()),
+            Raw::new(b"ICE".to_vec()),
+            Raw::new(b"IC".to_vec()),
+            Raw::new(b"ABC".to_vec()),
+        ];
+
+        let sorted: alloc::collections::BTreeSet<Raw> = candidates.iter().cloned().collect();
+        assert_eq!(sorted.into_iter().collect::<Vec<Raw>>(), vec![
+            Raw::new(b"ABC".to_vec()),
+            Raw::new(b"IC".to_vec()),
+            Raw::new(b"ICE".to_vec()),
+        ]);
+
+        // HashSet needs std, without it BTreeSet above covers deduplication
+        #[cfg(feature = "std")]
+        {
+            let unique: std::collections::HashSet<Raw> = candidates.into_iter().collect();
+            assert_eq!(unique.len(), 3);
+        }
+    }
+
+    #[test]
+    fn test_base64_decoder() {
+        let text = "SSdtIGtpbGxpbmcgeW91ciBicmFpbiBsaWtlIGEgcG9pc29ub3VzIG11c2hyb29t";
+        let expected = Base64::new(text.to_string()).unwrap().decode();
+
+        // every split point, so partial groups carry over at every offset
+        for i in 0..=text.len() {
+            let mut decoder = Base64Decoder::new();
+            decoder.push(&text[..i]).unwrap();
+            decoder.push(&text[i..]).unwrap();
+            assert_eq!(decoder.finish(), Ok(expected.clone()), "split at {i}");
+        }
+
+        let mut decoder = Base64Decoder::new();
+        for line in ["SUNF\n", "IQ=", "=\r\n"] {
+            decoder.push(line).unwrap();
+        }
+        assert_eq!(decoder.finish(), Ok(Raw::new(b"ICE!".to_vec())));
+
+        // unpadded is fine, as with Base64::new
+        let mut decoder = Base64Decoder::new();
+        decoder.push("SUNFIQ").unwrap();
+        assert_eq!(decoder.finish(), Ok(Raw::new(b"ICE!".to_vec())));
+
+        assert_eq!(Base64Decoder::new().finish(), Ok(Raw::new(vec![])));
+        assert_eq!(Base64Decoder::new().push("SU*F"), Err(DecodeError::new(DecodeErrorKind::InvalidChar('*'), 2)));
+        assert_eq!(Base64Decoder::new().push("SQ==SQ=="), Err(DecodeError::new(DecodeErrorKind::InvalidPadding, 2)));
+        assert_eq!(Base64Decoder::new().push("SQ==="), Err(DecodeError::new(DecodeErrorKind::InvalidPadding, 4)));
+
+        // positions count from the start of the first push
+        let mut decoder = Base64Decoder::new();
+        decoder.push("SUNF\n").unwrap();
+        assert_eq!(decoder.push("SU*F"), Err(DecodeError::new(DecodeErrorKind::InvalidChar('*'), 7)));
+
+        let mut decoder = Base64Decoder::new();
+        decoder.push("SUNFI").unwrap();
+        assert_eq!(decoder.finish(), Err(DecodeError::new(DecodeErrorKind::InvalidLength, 5)));
+
+        let mut decoder = Base64Decoder::new();
+        decoder.push("SUN=").unwrap();
+        assert_eq!(decoder.clone().finish(), Ok(Raw::new(b"IC".to_vec())));
+        decoder.push("=").unwrap();
+        assert_eq!(decoder.finish(), Err(DecodeError::new(DecodeErrorKind::InvalidLength, 5)));
+    }
+
+    #[test]
+    fn test_char_values() {
+        assert_eq!(Hex::nibble_value('a'), Some(10));
+        assert_eq!(Hex::nibble_value('F'), Some(15));
+        assert_eq!(Hex::nibble_value('g'), None);
+        assert_eq!(Hex::nibble_char(11), Some('b'));
+        assert_eq!(Hex::nibble_char(16), None);
+
+        assert_eq!(Base64::sextet_value('/'), Some(63));
+        assert_eq!(Base64::sextet_value('='), None);
+        assert_eq!(Base64::sextet_char(26), Some('a'));
+        assert_eq!(Base64::sextet_char(64), None);
+
+        for v in 0..16 {
+            assert_eq!(Hex::nibble_char(v).and_then(Hex::nibble_value), Some(v));
+        }
+
+        for v in 0..64 {
+            assert_eq!(Base64::sextet_char(v).and_then(Base64::sextet_value), Some(v));
+        }
+    }
+
+    #[test]
+    fn test_raw_random_seeded() {
+        assert_eq!(Raw::random_seeded(37, 7), Raw::random_seeded(37, 7));
+        assert_ne!(Raw::random_seeded(37, 7), Raw::random_seeded(37, 8));
+        assert_eq!(Raw::random_seeded(37, 7).len(), 37);
+        assert_eq!(Raw::random_seeded(0, 7), Raw::new(vec![]));
+
+        // a shorter sequence from the same seed is a prefix of the longer one
+        assert_eq!(Raw::random_seeded(5, 7).get(), &Raw::random_seeded(37, 7).get()[..5]);
+    }
+
+    #[test]
+    fn test_raw_random() {
+        assert_eq!(Raw::random(100).len(), 100);
+        assert_ne!(Raw::random(100), Raw::random(100));
+    }
+
+    #[test]
+    fn test_constructors_random_strings() {
+        // the same checks as the decode fuzz target, on strings mostly made of chars the
+        // constructors care about so a good share of them are accepted
+        let alphabet: Vec<char> = "0123456789abcdefABCDEF+/=xyz -\né".chars().collect();
+
+        for seed in 0..5000 {
+            let picks = Raw::random_seeded(seed as usize % 13, seed);
+            let s: String = picks.iter().map(|b| alphabet[*b as usize % alphabet.len()]).collect();
+
+            if let Some(hex) = Hex::new(s.clone()) {
+                let raw = hex.decode();
+                assert_eq!(Hex::encode(&raw).decode(), raw, "{s:?}");
+
+                if s.len().is_multiple_of(2) {
+                    assert_eq!(Hex::encode(&raw).as_str(), s.to_ascii_lowercase(), "{s:?}");
+                    assert_eq!(hex.try_decode(), Ok(raw), "{s:?}");
+                }
+            }
+
+            if let Some(b64) = Base64::new(s.clone()) {
+                let raw = b64.decode();
+                assert_eq!(Base64::encode(&raw).decode(), raw, "{s:?}");
+                assert_eq!(b64.try_decode(), Ok(raw), "{s:?}");
+            }
+
+            if let Some(ascii) = Ascii::new(s.clone()) {
+                assert_eq!(Ascii::encode(&ascii.decode()).as_str(), s, "{s:?}");
+            }
+
+            let _ = (Hex::new_lenient(&s), Base64::new_lenient(&s));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip_hex(bytes in prop::collection::vec(any::<u8>(), 0..200)) {
+            let raw = Raw::new(bytes);
+
+            prop_assert_eq!(Hex::encode(&raw).decode(), raw.clone());
+            prop_assert_eq!(Hex::try_from(Hex::encode(&raw).as_str()).map(Raw::from), Ok(raw));
+        }
+
+        #[test]
+        fn test_round_trip_base64(bytes in prop::collection::vec(any::<u8>(), 0..200)) {
+            let raw = Raw::new(bytes);
+
+            prop_assert_eq!(Base64::encode(&raw).decode(), raw.clone());
+            prop_assert_eq!(Base64::try_from(Base64::encode(&raw).as_str()).map(Raw::from), Ok(raw));
+        }
+
+        #[test]
+        fn test_round_trip_ascii(bytes in prop::collection::vec(0u8..0x80, 0..200)) {
+            let raw = Raw::new(bytes);
+
+            prop_assert_eq!(Ascii::encode(&raw).decode(), raw.clone());
+            prop_assert_eq!(Ascii::try_from(Ascii::encode(&raw).as_str()).map(Raw::from), Ok(raw));
+        }
+    }
+
+    #[test]
+    fn test_raw_eq_ignore_trailing_zeros() {
+        let ice = Raw::new(b"ICE".to_vec());
+
+        assert!(ice.eq_ignore_trailing_zeros(&Raw::new(b"ICE\0\0\0\0\0".to_vec())));
+        assert!(Raw::new(b"ICE\0".to_vec()).eq_ignore_trailing_zeros(&Raw::new(b"ICE\0\0".to_vec())));
+        assert!(Raw::new(vec![0, 0]).eq_ignore_trailing_zeros(&Raw::new(vec![])));
+
+        // only trailing zeros are ignored
+        assert!(!ice.eq_ignore_trailing_zeros(&Raw::new(b"\0ICE".to_vec())));
+        assert!(!ice.eq_ignore_trailing_zeros(&Raw::new(b"IC\0E".to_vec())));
+        assert!(!ice.eq_ignore_trailing_zeros(&Raw::new(b"ICE!".to_vec())));
+    }
+
+    #[test]
+    fn test_raw_strip_bom() {
+        assert_eq!(Raw::new(b"\xEF\xBB\xBFICE".to_vec()).strip_bom(), Raw::new(b"ICE".to_vec()));
+        assert_eq!(Raw::new(b"ICE\xEF\xBB\xBF".to_vec()).strip_bom(), Raw::new(b"ICE\xEF\xBB\xBF".to_vec()));
+        assert_eq!(Raw::new(b"\xEF\xBB".to_vec()).strip_bom(), Raw::new(b"\xEF\xBB".to_vec()));
+        assert_eq!(Raw::new(vec![]).strip_bom(), Raw::new(vec![]));
+    }
+
+    #[test]
+    fn test_raw_split_on() {
+        let raw = Raw::new(b"one\ntwo\n\nthree\n".to_vec());
+        let pieces: Vec<Raw> = ["one", "two", "", "three", ""].iter().map(|p| Raw::new(p.as_bytes().to_vec())).collect();
+
+        assert_eq!(raw.split_on(b'\n'), pieces);
+        assert_eq!(Raw::new(vec![]).split_on(b'\n'), vec![Raw::new(vec![])]);
+        assert_eq!(Raw::new(b"abc".to_vec()).split_on(b'\n'), vec![Raw::new(b"abc".to_vec())]);
+    }
+
+    #[test]
+    fn test_raw_split_on_slice() {
+        let raw = Raw::new(b"one\r\ntwo\r\n\r\nthree\r\n".to_vec());
+        let pieces: Vec<Raw> = ["one", "two", "", "three", ""].iter().map(|p| Raw::new(p.as_bytes().to_vec())).collect();
+
+        assert_eq!(raw.split_on_slice(b"\r\n"), pieces);
+
+        // matches don't overlap, the same as str::split
+        assert_eq!(
+            Raw::new(b"aaa".to_vec()).split_on_slice(b"aa"),
+            "aaa".split("aa").map(|p| Raw::new(p.as_bytes().to_vec())).collect::<Vec<Raw>>()
+        );
+
+        assert_eq!(raw.split_on_slice(b""), vec![raw.clone()]);
+        assert_eq!(Raw::new(b"ab".to_vec()).split_on_slice(b"abc"), vec![Raw::new(b"ab".to_vec())]);
+    }
+
+    #[test]
+    fn test_raw_hamming_blocks() {
+        let raw: Raw = Ascii::new("this is a testwokka wokka!!!".to_string()).unwrap().into();
+
+        assert_eq!(raw.hamming_blocks(14, 0, 1), Some(37));
+        assert_eq!(raw.hamming_blocks(14, 1, 0), Some(37));
+        assert_eq!(raw.hamming_blocks(14, 1, 1), Some(0));
+
+        // block 2 would run past the end, as would a partial block
+        assert_eq!(raw.hamming_blocks(14, 0, 2), None);
+        assert_eq!(raw.hamming_blocks(10, 0, 2), None);
+        assert_eq!(raw.hamming_blocks(0, 0, 1), None);
+        assert_eq!(raw.hamming_blocks(usize::MAX, 0, 1), None);
+    }
+
+    #[test]
+    fn test_raw_concat_slice_push() {
+        let mut raw = Raw::new(vec![0x01, 0x02]).concat(&Raw::new(vec![0x03]));
+        assert_eq!(raw, Raw::new(vec![0x01, 0x02, 0x03]));
+
+        raw.push(0x04);
+        assert_eq!(raw, Raw::new(vec![0x01, 0x02, 0x03, 0x04]));
+
+        assert_eq!(raw.slice(1..3), Some(Raw::new(vec![0x02, 0x03])));
+        assert_eq!(raw.slice(4..4), Some(Raw::new(vec![])));
+        assert_eq!(raw.slice(3..5), None);
+    }
+
+    #[test]
+    fn test_hex_eq_ignore_case() {
+        let raw = Raw::new(vec![0xde, 0xad, 0xbe, 0xef]);
+        let lower = Hex::encode(&raw);
+        let upper = Hex::encode_upper(&raw);
+
+        assert_ne!(lower, upper);
+        assert!(lower.eq_ignore_case(&upper));
+        assert!(Hex::new("DeAdBeEf".to_string()).unwrap().eq_ignore_case(&lower));
+        assert!(!lower.eq_ignore_case(&Hex::new("deadbeee".to_string()).unwrap()));
+    }
+
+    #[test]
+    fn test_raw_pad_truncate() {
+        let mut raw = Raw::new(b"ICE".to_vec());
+
+        // up to a whole number of 4 byte blocks, and never shorter
+        assert_eq!(raw.pad_to(raw.len().next_multiple_of(4), 0), Raw::new(b"ICE\0".to_vec()));
+        assert_eq!(raw.pad_to(6, b'!'), Raw::new(b"ICE!!!".to_vec()));
+        assert_eq!(raw.pad_to(2, b'!'), raw);
+
+        raw.truncate(5);
+        assert_eq!(raw, Raw::new(b"ICE".to_vec()));
+        raw.truncate(1);
+        assert_eq!(raw, Raw::new(b"I".to_vec()));
+    }
+
+    #[test]
+    fn test_raw_chunks() {
+        let raw = Raw::new(vec![0, 1, 2, 3, 4, 5, 6]);
+
+        let chunks: Vec<Raw> = raw.chunks(3).collect();
+        assert_eq!(chunks, vec![Raw::new(vec![0, 1, 2]), Raw::new(vec![3, 4, 5]), Raw::new(vec![6])]);
+
+        assert_eq!(raw.chunks(7).count(), 1);
+        assert_eq!(Raw::new(vec![]).chunks(3).count(), 0);
+    }
+
+    #[test]
+    fn test_try_from_str() {
+        assert_eq!(Hex::try_from("deadBEEF"), Ok(Hex::new("deadBEEF".to_string()).unwrap()));
+        assert_eq!(Hex::try_from("dead beef"), Err(DecodeError::new(DecodeErrorKind::InvalidChar(' '), 4)));
+        assert_eq!(Hex::try_from("abc"), Err(DecodeError::new(DecodeErrorKind::InvalidLength, 3)));
+
+        assert_eq!(Base64::try_from("SUNF"), Ok(Base64::new("SUNF".to_string()).unwrap()));
+        assert_eq!(Base64::try_from("SU*F"), Err(DecodeError::new(DecodeErrorKind::InvalidChar('*'), 2)));
+        assert_eq!(Base64::try_from("SUNFS"), Err(DecodeError::new(DecodeErrorKind::InvalidLength, 5)));
+        assert_eq!(Base64::try_from("S=NF"), Err(DecodeError::new(DecodeErrorKind::InvalidPadding, 1)));
+        assert_eq!(Base64::try_from("SQ==="), Err(DecodeError::new(DecodeErrorKind::InvalidPadding, 4)));
+
+        assert_eq!(Base64Url::try_from("SU-_").map(|b| b.into_string()), Ok("SU-_".to_string()));
+        assert_eq!(Base64Url::try_from("SU+F"), Err(DecodeError::new(DecodeErrorKind::InvalidChar('+'), 2)));
+        assert_eq!(Base32::try_from("JFBUKQ=="), Err(DecodeError::new(DecodeErrorKind::InvalidLength, 8)));
+        assert_eq!(Base32::try_from("JFBUK1"), Err(DecodeError::new(DecodeErrorKind::InvalidChar('1'), 5)));
+        assert_eq!(Binary::try_from("0100100"), Err(DecodeError::new(DecodeErrorKind::InvalidLength, 7)));
+        assert_eq!(Binary::try_from("01001002"), Err(DecodeError::new(DecodeErrorKind::InvalidChar('2'), 7)));
+
+        assert_eq!(Ascii::try_from("ICE"), Ok(Ascii::new("ICE".to_string()).unwrap()));
+        assert_eq!(Ascii::try_from("ICÉ"), Err(DecodeError::new(DecodeErrorKind::InvalidChar('É'), 2)));
+    }
+
+    #[test]
+    fn test_raw_format_dispatch() {
+        let raw = Raw::new(b"ICE".to_vec());
+
+        for (fmt, encoded) in [
+            (Format::Hex, "494345"),
+            (Format::Base64, "SUNF"),
+            (Format::Ascii, "ICE"),
+            (Format::Base64Url, "SUNF"),
+            (Format::Base32, "JFBUK==="),
+            (Format::Binary, "010010010100001101000101"),
+        ] {
+            assert_eq!(raw.encode_as(fmt), encoded);
+            assert_eq!(Raw::decode_from(encoded, fmt), Some(raw.clone()));
+        }
+
+        assert_eq!(Raw::decode_from("abc", Format::Hex), None);
+        assert_eq!(Raw::decode_from("SU*F", Format::Base64), None);
+        assert_eq!(Raw::decode_from("ICÉ", Format::Ascii), None);
+        assert_eq!(Raw::decode_from("SU+F", Format::Base64Url), None);
+        assert_eq!(Raw::decode_from("JFBUK1", Format::Base32), None);
+        assert_eq!(Raw::decode_from("0100100", Format::Binary), None);
+    }
+
+    #[test]
+    fn test_format_names() {
+        for fmt in Format::ALL {
+            assert_eq!(Format::from_name(fmt.name()), Some(fmt));
+        }
+
+        assert_eq!(Format::from_name("base64url"), Some(Format::Base64Url));
+        assert_eq!(Format::from_name("raw"), None);
+        assert_eq!(Format::from_name("Hex"), None);
+    }
+
+    #[test]
+    fn test_raw_decode_auto() {
+        assert_eq!(Raw::decode_auto("494345"), (Raw::new(b"ICE".to_vec()), Format::Hex));
+        assert_eq!(Raw::decode_auto("SUNFIQ=="), (Raw::new(b"ICE!".to_vec()), Format::Base64));
+        assert_eq!(Raw::decode_auto("ICE ICE baby"), (Raw::new(b"ICE ICE baby".to_vec()), Format::Ascii));
+
+        // valid base64 too, but hex is tried first
+        assert_eq!(Raw::decode_auto("deadbeef").1, Format::Hex);
+
+        // odd length hex and unpadded base64 fall through
+        assert_eq!(Raw::decode_auto("abc").1, Format::Ascii);
+        assert_eq!(Raw::decode_auto("SUNFIQ").1, Format::Ascii);
+        assert_eq!(Raw::decode_auto(""), (Raw::new(vec![]), Format::Ascii));
+    }
+
+    #[test]
+    fn test_raw_str_shortcuts() {
+        let raw = Raw::new(b"ICE".to_vec());
+
+        assert_eq!(Raw::from_hex_str("494345"), Some(raw.clone()));
+        assert_eq!(Raw::from_hex_str("49434"), None);
+        assert_eq!(Raw::from_hex_str("zz"), None);
+        assert_eq!(Raw::from_base64_str("SUNF"), Some(raw.clone()));
+        assert_eq!(Raw::from_base64_str("SUN*"), None);
+
+        assert_eq!(raw.to_hex_string(), "494345");
+        assert_eq!(raw.to_base64_string(), "SUNF");
+    }
 
+    #[test]
+    fn test_raw_iterators() {
+        let raw = Raw::new(vec![0x01, 0x02, 0x03]);
+
+        let doubled: Raw = (&raw).into_iter().map(|b| b * 2).collect();
+        assert_eq!(doubled, Raw::new(vec![0x02, 0x04, 0x06]));
+
+        let mut sum = 0;
+        for b in &raw {
+            sum += b;
+        }
+        assert_eq!(sum, 6);
+
+        assert_eq!(raw.into_iter().rev().collect::<Raw>(), Raw::new(vec![0x03, 0x02, 0x01]));
+        assert_eq!(core::iter::empty().collect::<Raw>(), Raw::new(vec![]));
+    }
+
+    #[test]
+    fn test_ascii_from_bytes() {
+        assert_eq!(Ascii::from_bytes(b"ICE ICE"), Ascii::new("ICE ICE".to_string()));
+        assert_eq!(Ascii::from_bytes(&[]), Ascii::new(String::new()));
+        assert_eq!(Ascii::from_bytes(&[b'a', 0x80]), None);
+
+        // not valid UTF-8 either, but that doesn't matter
+        assert_eq!(Ascii::from_bytes(&[0xff]), None);
+
+        assert_eq!(Ascii::try_from(b"ICE".to_vec()), Ok(Ascii::new("ICE".to_string()).unwrap()));
+        assert_eq!(Ascii::try_from(vec![b'a', 0xe9]), Err(DecodeError::new(DecodeErrorKind::InvalidChar('é'), 1)));
+    }
+
+    #[test]
+    fn test_ascii_from_raw_lossy() {
+        let raw = Raw::new(b"Cooking\x00MC's\r\n\tlike \xffa pound".to_vec());
+
+        assert_eq!(Ascii::from_raw_lossy(&raw), "Cooking.MC's.\n\tlike .a pound");
+        assert_eq!(Ascii::from_raw_lossy(&Raw::new(vec![])), "");
+    }
+
+    #[test]
+    fn test_raw_smallest_period() {
+        let abc = Raw::new(b"ABC".to_vec());
+
+        assert_eq!(Raw::new(b"ABCABC".to_vec()).smallest_period(), abc);
+        assert_eq!(abc.smallest_period(), abc);
+        assert_eq!(Raw::new(b"AAAA".to_vec()).smallest_period(), Raw::new(b"A".to_vec()));
+
+        // a partial repetition isn't collapsed
+        assert_eq!(Raw::new(b"ABCAB".to_vec()).smallest_period(), Raw::new(b"ABCAB".to_vec()));
+        assert_eq!(Raw::new(vec![]).smallest_period(), Raw::new(vec![]));
+    }
+
+    #[test]
+    fn test_raw_is_empty() {
+        assert!(Raw::new(vec![]).is_empty());
+        assert!(!Raw::new(vec![0x00]).is_empty());
     }
 }
@@ -0,0 +1,191 @@
+//! Readers for the encoded input files that challenges tend to come in, and xor for files too
+//! big to read in whole
+
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+
+#[cfg(not(feature = "mmap"))]
+use std::io::Read;
+
+use crate::{Base64, Hex, Raw};
+
+fn invalid_data(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// read a text file, dropping a leading UTF-8 BOM first
+fn read_text<P: AsRef<Path>>(path: P) -> io::Result<String> {
+    let raw = Raw::new(fs::read(path)?).strip_bom();
+
+    String::from_utf8(raw.get().to_vec()).map_err(|_| invalid_data("file isn't valid UTF-8".to_string()))
+}
+
+/// read a file with one hex encoded sequence per line. Blank lines are skipped and whitespace
+/// around each line is ignored, as is a UTF-8 BOM at the start
+pub fn read_hex_lines<P: AsRef<Path>>(path: P) -> io::Result<Vec<Raw>> {
+    let text = read_text(path)?;
+
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            Hex::new_lenient(line)
+                .map(Raw::from)
+                .ok_or_else(|| invalid_data(format!("line {} isn't valid hex", i + 1)))
+        })
+        .collect()
+}
+
+/// read a file holding a single base64 encoded sequence, which may be wrapped over several lines
+/// (as in PEM files). A UTF-8 BOM at the start is ignored
+pub fn read_base64_file<P: AsRef<Path>>(path: P) -> io::Result<Raw> {
+    let text = read_text(path)?;
+
+    Base64::new_lenient(&text)
+        .map(Raw::from)
+        .ok_or_else(|| invalid_data("file isn't valid base64".to_string()))
+}
+
+/// xor one chunk of a file that starts pos bytes into the key. The key is rotated once to start
+/// there, so the chunk itself goes through xor's word at a time path
+fn xor_chunk(chunk: &[u8], key: &Raw, pos: usize) -> Raw {
+    let rotated: Raw = key.iter().cycle().skip(pos).take(key.len()).copied().collect();
+
+    crate::xor(&Raw::from(chunk), &rotated).expect("key is checked to be non-empty")
+}
+
+/// how much of a file xor_file works on at once
+const CHUNK: usize = 64 * 1024;
+
+/// xor a file against a cyclical key into output, a chunk at a time so memory use stays flat
+/// however large the input is. The key carries on from where the previous chunk left off, so the
+/// result matches xor on the whole file. With the mmap feature the input is memory mapped rather
+/// than read. Fails with InvalidInput for an empty key
+pub fn xor_file<P: AsRef<Path>, Q: AsRef<Path>>(input: P, output: Q, key: &Raw) -> io::Result<()> {
+    if key.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, crate::EmptyKey.to_string()));
+    }
+
+    let reader = fs::File::open(input)?;
+    let mut writer = io::BufWriter::new(fs::File::create(output)?);
+
+    xor_chunks(reader, &mut writer, key)?;
+
+    writer.flush()
+}
+
+#[cfg(not(feature = "mmap"))]
+/// read the file a chunk at a time and write each one out xor'd
+fn xor_chunks(mut reader: fs::File, writer: &mut impl Write, key: &Raw) -> io::Result<()> {
+    let mut buf = vec![0; CHUNK];
+    let mut pos = 0;
+
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+
+        writer.write_all(xor_chunk(&buf[..n], key, pos).get())?;
+        pos = (pos + n) % key.len();
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "mmap")]
+/// map the file and write each chunk of the map out xor'd, leaving the paging to the OS
+fn xor_chunks(reader: fs::File, writer: &mut impl Write, key: &Raw) -> io::Result<()> {
+    // some platforms refuse to map an empty file, and there's nothing to xor anyway
+    if reader.metadata()?.len() == 0 {
+        return Ok(());
+    }
+
+    // SAFETY: the map is only read, and dropped before xor_file returns. Another process
+    // truncating the file while it's mapped would fault, which no mapping can guard against
+    let map = unsafe { memmap2::Mmap::map(&reader)? };
+
+    for (i, chunk) in map.chunks(CHUNK).enumerate() {
+        writer.write_all(xor_chunk(chunk, key, i * CHUNK % key.len()).get())?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temp_file(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("rxor-tools-{}-{name}", std::process::id()));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_read_hex_lines() {
+        let path = temp_file("hex-lines", "49 43 45\n\n  0a0B\r\n");
+        let lines = read_hex_lines(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(lines, vec![Raw::new(b"ICE".to_vec()), Raw::new(vec![0x0a, 0x0b])]);
+
+        let path = temp_file("hex-lines-bom", "\u{FEFF}494345\n");
+        assert_eq!(read_hex_lines(&path).unwrap(), vec![Raw::new(b"ICE".to_vec())]);
+        fs::remove_file(&path).unwrap();
+
+        let path = temp_file("hex-lines-bad", "4943\nzz\n");
+        let err = read_hex_lines(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(read_hex_lines("/nonexistent/rxor-tools").is_err());
+    }
+
+    #[test]
+    fn test_xor_file() {
+        // a couple of chunks plus a bit, with a key length that doesn't divide the chunk size
+        let plain: Vec<u8> = (0..150_000u32).map(|i| (i % 251) as u8).collect();
+        let key = Raw::new(b"VANILLA".to_vec());
+
+        let input = std::env::temp_dir().join(format!("rxor-tools-{}-xor-in", std::process::id()));
+        let output = std::env::temp_dir().join(format!("rxor-tools-{}-xor-out", std::process::id()));
+        fs::write(&input, &plain).unwrap();
+
+        xor_file(&input, &output, &key).unwrap();
+        let encrypted = fs::read(&output).unwrap();
+
+        assert_eq!(Raw::new(encrypted), crate::xor(&Raw::new(plain), &key).unwrap());
+        assert_eq!(xor_file(&input, &output, &Raw::new(vec![])).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+
+        fs::write(&input, b"").unwrap();
+        xor_file(&input, &output, &key).unwrap();
+        assert!(fs::read(&output).unwrap().is_empty());
+
+        fs::remove_file(&input).unwrap();
+        fs::remove_file(&output).unwrap();
+    }
+
+    #[test]
+    fn test_read_base64_file() {
+        let path = temp_file("base64", "SSdtIGtp\nbGxpbmcg\neW91cg==\n");
+        let raw = read_base64_file(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(raw, Raw::new(b"I'm killing your".to_vec()));
+
+        let path = temp_file("base64-bom", "\u{FEFF}SUNF\r\n");
+        assert_eq!(read_base64_file(&path).unwrap(), Raw::new(b"ICE".to_vec()));
+        fs::remove_file(&path).unwrap();
+
+        let path = temp_file("base64-bad", "SSdt*\n");
+        assert_eq!(read_base64_file(&path).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,67 +1,903 @@
 //! Repeating XOR Cipher Toolset
+//!
+//! The encodings and xor primitives only need `alloc`, so the crate builds as `no_std` with the
+//! default `std` feature turned off. Scoring, the attacks that rely on it and the file readers
+//! need `std`.
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::{format, string::String, vec, vec::Vec};
+
+#[cfg(feature = "std")]
+pub mod analysis;
+pub mod caesar;
 pub mod encodings;
+#[cfg(feature = "std")]
+pub mod files;
+pub mod prelude;
+#[cfg(feature = "serde")]
+pub mod serde_raw;
+#[cfg(feature = "std")]
+pub use analysis::*;
 pub use encodings::*;
+#[cfg(feature = "std")]
+pub use files::*;
 
-/// xor a sequence against a fixed length cyclical key
-pub fn xor(seq: &Raw, key: &Raw) -> Raw {
-    let mut ki = 0;
-    let mut r = Vec::new();
+/// Error for xor'ing against a key with no bytes in it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EmptyKey;
 
-    for s in seq.iter() {
-        r.push(s ^ key[ki]);
-        ki = (ki + 1) % key.len();
+impl core::fmt::Display for EmptyKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "xor key is empty")
     }
+}
 
-    Raw::new(r)
+impl core::error::Error for EmptyKey {}
+
+/// Error for elementwise xor of two sequences that aren't the same length
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LenMismatch {
+    pub left: usize,
+    pub right: usize,
 }
 
-/// attempt to guess the key length of a given encrypted sequence
-pub fn find_key_len(raw : &Raw, llim: usize, hlim: usize) -> Vec<usize> {
-    assert!(llim <= hlim);
-    let mut distances = Vec::new();
-    for _ in llim..=hlim {
-        distances.push(0.0f32);
+impl core::fmt::Display for LenMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "sequences differ in length ({} and {} bytes)", self.left, self.right)
     }
+}
 
-    let mut guesses = Vec::new();
+impl core::error::Error for LenMismatch {}
+
+/// a repeating xor key, checked once so encrypting and decrypting with it can't fail
+#[derive(Clone, Debug, PartialEq)]
+pub struct XorCipher {
+    key: Raw,
+}
 
-    // compute hamming distances for each key len
-    for n in llim..=hlim {
-        let blocks = raw.len() / n;
+impl XorCipher {
+    /// fails if the key is empty
+    pub fn new(key: Raw) -> Result<Self, EmptyKey> {
+        if key.is_empty() {
+            return Err(EmptyKey);
+        }
+
+        Ok(Self { key })
+    }
+
+    /// borrow the key
+    pub fn key(&self) -> &Raw {
+        &self.key
+    }
+
+    /// xor the plaintext against the key
+    pub fn encrypt(&self, plaintext: &Raw) -> Raw {
+        xor(plaintext, &self.key).expect("key is checked in new")
+    }
+
+    /// xor is its own inverse, so this is the same as encrypt
+    pub fn decrypt(&self, ciphertext: &Raw) -> Raw {
+        self.encrypt(ciphertext)
+    }
+}
+
+/// xor a sequence against a fixed length cyclical key, failing if the key is empty
+pub fn xor(seq: &Raw, key: &Raw) -> Result<Raw, EmptyKey> {
+    if key.is_empty() {
+        return Err(EmptyKey);
+    }
+
+    if key.len() == 1 {
+        return Ok(xor_byte(seq, key[0]));
+    }
+
+    Ok(xor_cycle_precomputed(seq.get(), key.get()))
+}
 
-        // assuming key is smaller than 1/2 the input size
-        if blocks < 2 {
-            distances[n - llim] = f32::INFINITY;
+/// the key repeated out to lcm(key length, word size) bytes is a whole number of both keys and
+/// words, so each block of that length is xor'd a word at a time against the same precomputed
+/// words. The tail shorter than a block starts back at key[0] and is done byte by byte, as is
+/// all of an input too short to fill one block
+fn xor_cycle_precomputed(seq: &[u8], key: &[u8]) -> Raw {
+    const WORD: usize = core::mem::size_of::<u64>();
+
+    fn gcd(a: usize, b: usize) -> usize {
+        if b == 0 {
+            a
         } else {
-            // single block algorithm (todo! could be expanded to multiple block average)
-            let s0: Raw = raw.get()[0..n].into();
-            let s1: Raw = raw.get()[n..(2*n)].into();
+            gcd(b, a % b)
+        }
+    }
+
+    let block = key.len() / gcd(key.len(), WORD) * WORD;
+
+    // an odd key's block is 8 keys long, not worth building for an input that can't use it
+    if seq.len() < block {
+        return seq.iter().zip(key.iter().cycle()).map(|(s, k)| s ^ k).collect();
+    }
+
+    let key_words: Vec<u64> = key
+        .iter()
+        .cycle()
+        .take(block)
+        .copied()
+        .collect::<Vec<u8>>()
+        .chunks_exact(WORD)
+        .map(|w| u64::from_ne_bytes(w.try_into().expect("chunks are exactly one word")))
+        .collect();
+
+    let mut r = Vec::with_capacity(seq.len());
+    let mut blocks = seq.chunks_exact(block);
+
+    for b in &mut blocks {
+        for (word, k) in b.chunks_exact(WORD).zip(&key_words) {
+            let word = u64::from_ne_bytes(word.try_into().expect("chunks are exactly one word"));
+            r.extend_from_slice(&(word ^ k).to_ne_bytes());
+        }
+    }
+
+    r.extend(blocks.remainder().iter().zip(key.iter().cycle()).map(|(s, k)| s ^ k));
+
+    Raw::new(r)
+}
+
+impl Raw {
+    /// xor against a cyclical key in place, like xor but without allocating. Fails (leaving the
+    /// bytes untouched) if the key is empty
+    pub fn xor_assign(&mut self, key: &Raw) -> Result<(), EmptyKey> {
+        if key.is_empty() {
+            return Err(EmptyKey);
+        }
 
-            distances[n - llim] = s0.hamming_normalized(&s1);
+        for (s, k) in self.get_mut().iter_mut().zip(key.iter().cycle()) {
+            *s ^= k;
         }
+
+        Ok(())
+    }
+}
+
+/// `a ^ b` is elementwise xor of two equal length sequences, as in fixed_xor. It panics if the
+/// lengths differ; use fixed_xor to handle that, or xor for a repeating key
+impl core::ops::BitXor<&Raw> for &Raw {
+    type Output = Raw;
+
+    fn bitxor(self, rhs: &Raw) -> Raw {
+        match fixed_xor(self, rhs) {
+            Ok(raw) => raw,
+            Err(e) => panic!("{e}"),
+        }
+    }
+}
+
+/// show how a repeating key lines up against a sequence, 16 bytes per row: the row's offset and
+/// the sequence's bytes in hex, with the key byte each one gets xor'd with underneath. Fails if
+/// the key is empty
+///
+/// ```text
+/// 00000000  0b 36 37 27 2a 2b 2e 63 62 2c 2e 69 69 2a 23 69
+///       key 49 43 45 49 43 45 49 43 45 49 43 45 49 43 45 49
+/// ```
+pub fn format_key_alignment(cipher: &Raw, key: &Raw) -> Result<String, EmptyKey> {
+    if key.is_empty() {
+        return Err(EmptyKey);
     }
 
+    let cycled: Raw = key.iter().cycle().take(cipher.len()).copied().collect();
+    let mut out = String::new();
+
+    for (row, (c, k)) in cipher.chunks(16).zip(cycled.chunks(16)).enumerate() {
+        out.push_str(&format!("{:08x}  {}\n", row * 16, Hex::encode_with_sep(&c, " ")));
+        out.push_str(&format!("      key {}\n", Hex::encode_with_sep(&k, " ")));
+    }
+
+    Ok(out)
+}
+
+/// xor a sequence against a cyclical key a machine word at a time. xor itself does this for
+/// every key length now, so this is the same as calling it
+#[deprecated(note = "xor works a word at a time for every key length, use it instead")]
+pub fn xor_wide(seq: &Raw, key: &Raw) -> Result<Raw, EmptyKey> {
+    xor(seq, key)
+}
+
+/// xor a sequence against a cyclical key without a modulo or branch per byte: whole key cycles
+/// are xor'd in lockstep with the key, then the tail against the start of the key. The work done
+/// only depends on the lengths, never the byte values
+pub fn xor_ct(seq: &Raw, key: &Raw) -> Result<Raw, EmptyKey> {
+    if key.is_empty() {
+        return Err(EmptyKey);
+    }
+
+    let mut r = Vec::with_capacity(seq.len());
+    let mut cycles = seq.get().chunks_exact(key.len());
+
+    for cycle in &mut cycles {
+        r.extend(cycle.iter().zip(key.iter()).map(|(s, k)| s ^ k));
+    }
+
+    r.extend(cycles.remainder().iter().zip(key.iter()).map(|(s, k)| s ^ k));
+
+    Ok(Raw::new(r))
+}
+
+/// lazily xor a stream of bytes against a cyclical key, so large inputs (e.g. a BufReader's
+/// bytes) don't need to be collected first. The key position carries on across however the
+/// stream happens to be chunked
+pub fn xor_stream<'k, I: Iterator<Item = u8> + 'k>(
+    seq: I,
+    key: &'k [u8],
+) -> Result<impl Iterator<Item = u8> + 'k, EmptyKey> {
+    if key.is_empty() {
+        return Err(EmptyKey);
+    }
+
+    Ok(seq.zip(key.iter().cycle()).map(|(s, k)| s ^ k))
+}
+
+/// xor a sequence against bytes drawn from any keystream, e.g. a PRNG or counter mode
+/// generator; a repeating key is just key.iter().cycle(). None if the keystream runs out before
+/// the sequence does
+pub fn xor_keystream<I: Iterator<Item = u8>>(seq: &Raw, mut keystream: I) -> Option<Raw> {
+    seq.iter().map(|s| keystream.next().map(|k| s ^ k)).collect()
+}
+
+/// xor a sequence against a passphrase used directly as the repeating key. Non ASCII characters
+/// contribute their UTF-8 bytes. An empty passphrase is an EmptyKey error, same as xor
+pub fn xor_with_passphrase(seq: &Raw, passphrase: &str) -> Result<Raw, EmptyKey> {
+    xor(seq, &Raw::new(passphrase.as_bytes().to_vec()))
+}
+
+/// repeating key xor of ASCII plaintext, hex encoded (Cryptopals challenge 5). None if either
+/// string isn't ASCII or the key is empty
+pub fn encrypt_to_hex(plaintext: &str, key: &str) -> Option<Hex> {
+    let plaintext = Raw::from(Ascii::new(plaintext.into())?);
+    let key = Raw::from(Ascii::new(key.into())?);
+
+    xor(&plaintext, &key).ok().map(Hex::from)
+}
+
+/// xor two equal length sequences byte by byte, failing if their lengths differ. Unlike xor,
+/// b is never repeated to cover a
+pub fn fixed_xor(a: &Raw, b: &Raw) -> Result<Raw, LenMismatch> {
+    if a.len() != b.len() {
+        return Err(LenMismatch { left: a.len(), right: b.len() });
+    }
+
+    Ok(a.into_iter().zip(b).map(|(l, r)| l ^ r).collect())
+}
+
+/// xor every byte of a sequence against a single key byte
+pub fn xor_byte(seq: &Raw, key: u8) -> Raw {
+    seq.into_iter().map(|b| b ^ key).collect()
+}
+
+/// attempt to guess the key length of a given encrypted sequence. A llim of 0 is treated as 1
+pub fn find_key_len(raw : &Raw, llim: usize, hlim: usize) -> Vec<usize> {
+    let scored = find_key_len_scored(raw, llim, hlim);
+
     // get top 5% or set
     let top_n = ((hlim - llim) as f32 * 0.05).max(1.0) as usize;
 
-    for _ in 0..top_n {
-        let mut best = (f32::INFINITY, 0usize);
-        for (i, d) in distances.iter().enumerate() {
-            if *d < best.0 && !guesses.contains(&i) {
-                best = (*d, i + llim);
-            }
+    scored
+        .into_iter()
+        .take(top_n)
+        .map(|(n, _)| n)
+        .collect()
+}
+
+/// the single most likely key length in llim..=hlim, but only if its find_key_len_scored distance
+/// beats the runner up's by at least confidence_ratio (e.g. 1.2 means the runner up has to be 20%
+/// further off). None when it's too close to call or nothing in the range fits the input
+pub fn find_key_len_best(raw: &Raw, llim: usize, hlim: usize, confidence_ratio: f32) -> Option<usize> {
+    let scored = find_key_len_scored(raw, llim, hlim);
+    let (best, best_distance) = *scored.first()?;
+
+    match scored.get(1) {
+        Some((_, second)) if *second <= best_distance || *second < best_distance * confidence_ratio => None,
+        _ => Some(best),
+    }
+}
+
+/// score every key length in llim..=hlim by its average normalized hamming distance between
+/// blocks, sorted with the most likely (lowest) first. Lengths over half the input can't fill two
+/// blocks to compare, so they're left out (empty if none of the range fits), as is 0
+pub fn find_key_len_scored(raw : &Raw, llim: usize, hlim: usize) -> Vec<(usize, f32)> {
+    assert!(llim <= hlim);
+
+    let mut distances: Vec<(usize, f32)> = (llim..=hlim)
+        .zip(key_len_profile(raw, llim, hlim))
+        .filter(|(n, _)| (1..=raw.len() / 2).contains(n))
+        .collect();
+
+    // stable, so ties keep the shorter length first
+    distances.sort_by(|l, r| l.1.total_cmp(&r.1));
+
+    distances
+}
+
+/// the average normalized hamming distance for every key length in llim..=hlim, unsorted, with
+/// the distance for length n at index n - llim (lower is more likely). Length 0 and lengths over
+/// half the input can't fill two blocks to compare and get infinity. Handy for plotting how
+/// clearly the right length stands out
+pub fn key_len_profile(raw: &Raw, llim: usize, hlim: usize) -> Vec<f32> {
+    assert!(llim <= hlim);
+
+    // each length is scored independently, so with rayon they're spread across threads. The
+    // collected order matches the sequential one either way
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+
+        (llim..=hlim).into_par_iter().map(|n| key_len_distance(raw, n)).collect()
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    {
+        (llim..=hlim).map(|n| key_len_distance(raw, n)).collect()
+    }
+}
+
+#[cfg(feature = "std")]
+/// index of coincidence of English text, see Raw::index_of_coincidence
+const ENGLISH_IOC: f32 = 0.0667;
+
+#[cfg(feature = "std")]
+/// score every key length in llim..=hlim by the average index of coincidence of its transposed
+/// columns, sorted with the lengths closest to English first. Each column of the right length is
+/// single byte xor'd English, which keeps English's IoC, while wrong lengths mix key bytes and
+/// flatten it. Less noisy than find_key_len_scored on short inputs. A llim of 0 is treated as 1.
+/// Lengths over half the input leave columns too short to have a meaningful IoC, so like
+/// find_key_len_scored they're left out (empty if none of the range fits)
+pub fn find_key_len_ioc(raw: &Raw, llim: usize, hlim: usize) -> Vec<(usize, f32)> {
+    assert!(llim <= hlim);
+
+    let mut iocs: Vec<(usize, f32)> = (llim.max(1)..=hlim.min(raw.len() / 2))
+        .map(|n| {
+            let columns = transpose(raw, n);
+            let total: f32 = columns.iter().map(Raw::index_of_coincidence).sum();
+
+            (n, total / n as f32)
+        })
+        .collect();
+
+    // stable, so ties keep the shorter length first
+    iocs.sort_by(|l, r| (l.1 - ENGLISH_IOC).abs().total_cmp(&(r.1 - ENGLISH_IOC).abs()));
+
+    iocs
+}
+
+#[cfg(feature = "std")]
+/// rank key lengths in llim..=hlim by both find_key_len_scored and find_key_len_ioc, weighting
+/// them equally. See find_key_len_weighted
+pub fn find_key_len_combined(raw: &Raw, llim: usize, hlim: usize) -> Vec<(usize, f32)> {
+    find_key_len_weighted(raw, llim, hlim, 0.5)
+}
+
+#[cfg(feature = "std")]
+/// rank key lengths in llim..=hlim by a weighted average of where they place under hamming
+/// distance (find_key_len_scored) and index of coincidence (find_key_len_ioc). Each length gets
+/// its rank under both metrics scaled to 0.0 (best) - 1.0 (worst), then hamming_weight of the
+/// hamming rank plus the rest of the IoC rank. Sorted with the lowest (most likely) first
+pub fn find_key_len_weighted(raw: &Raw, llim: usize, hlim: usize, hamming_weight: f32) -> Vec<(usize, f32)> {
+    let hamming = find_key_len_scored(raw, llim, hlim);
+
+    // same lengths as the hamming ranking, which leaves out the ones that don't fit twice
+    let ioc = match hamming.iter().map(|(n, _)| *n).max() {
+        Some(max) => find_key_len_ioc(raw, llim, max),
+        None => return Vec::new(),
+    };
+
+    let scale = (hamming.len() - 1).max(1) as f32;
+    let rank = |ranked: &[(usize, f32)], n: usize| {
+        ranked.iter().position(|(m, _)| *m == n).expect("both rankings cover the same lengths") as f32 / scale
+    };
+
+    let mut combined: Vec<(usize, f32)> = hamming
+        .iter()
+        .map(|(n, _)| (*n, hamming_weight * rank(&hamming, *n) + (1.0 - hamming_weight) * rank(&ioc, *n)))
+        .collect();
+
+    // ties keep the shorter length first
+    combined.sort_by(|l, r| l.1.total_cmp(&r.1).then(l.0.cmp(&r.0)));
+
+    combined
+}
+
+/// average normalized hamming distance between adjacent n byte blocks, or infinity if the input
+/// doesn't hold two blocks
+fn key_len_distance(raw: &Raw, n: usize) -> f32 {
+    // a short trailing block would skew its comparison, so only whole blocks count
+    let blocks = raw.len().checked_div(n).unwrap_or(0);
+
+    // assuming key is smaller than 1/2 the input size
+    if blocks < 2 {
+        return f32::INFINITY;
+    }
+
+    // average over every pair of adjacent blocks, a single pair is too noisy to rely on
+    let total: usize = (0..blocks - 1)
+        .map(|i| raw.hamming_blocks(n, i, i + 1).expect("both blocks are whole"))
+        .sum();
+
+    total as f32 / (8 * n * (blocks - 1)) as f32
+}
+
+#[cfg(feature = "std")]
+/// break a single byte xor cipher, returning the key byte, the decrypted sequence and its
+/// english_score (lower is better)
+pub fn break_single_byte(raw: &Raw) -> (u8, Raw, f32) {
+    break_single_byte_with(raw, english_score)
+}
+
+/// break a single byte xor cipher, ranking candidates with the given scorer (lower is better,
+/// e.g. english_score, quadgram_score or base64_score)
+pub fn break_single_byte_with(raw: &Raw, scorer: impl Fn(&Raw) -> f32) -> (u8, Raw, f32) {
+    // there are always 256 candidates, so the best one exists
+    break_single_byte_topn_with(raw, 1, scorer).remove(0)
+}
+
+#[cfg(feature = "std")]
+/// the n best (key byte, decrypted sequence, english_score) candidates for a single byte xor
+/// cipher, best first
+pub fn break_single_byte_topn(raw: &Raw, n: usize) -> Vec<(u8, Raw, f32)> {
+    break_single_byte_topn_with(raw, n, english_score)
+}
+
+/// the n best candidates for a single byte xor cipher according to the given scorer, best first.
+/// Candidates with equal scores are ordered by key byte
+pub fn break_single_byte_topn_with(raw: &Raw, n: usize, scorer: impl Fn(&Raw) -> f32) -> Vec<(u8, Raw, f32)> {
+    let mut candidates: Vec<(u8, Raw, f32)> = (0..=255u8)
+        .map(|k| {
+            let candidate = xor_byte(raw, k);
+            let score = scorer(&candidate);
+            (k, candidate, score)
+        })
+        .collect();
+
+    candidates.sort_by(|a, b| a.2.total_cmp(&b.2));
+    candidates.truncate(n);
+
+    candidates
+}
+
+#[cfg(feature = "std")]
+/// find the one sequence out of many that was single byte xor'd, by breaking each of them and
+/// keeping the most English result. Returns (index, key byte, plaintext, score), or None if
+/// there weren't any lines
+pub fn find_xored_line(lines: &[Raw]) -> Option<(usize, u8, Raw, f32)> {
+    let mut best: Option<(usize, u8, Raw, f32)> = None;
+
+    for (i, line) in lines.iter().enumerate() {
+        let (key, plaintext, score) = break_single_byte(line);
+
+        if best.as_ref().is_none_or(|b| score < b.3) {
+            best = Some((i, key, plaintext, score));
         }
+    }
+
+    best
+}
+
+/// split a sequence into key_len blocks, where block i holds every byte that was xor'd against
+/// key position i. Trailing blocks are one byte shorter when the length isn't a multiple of key_len
+pub fn transpose(raw: &Raw, key_len: usize) -> Vec<Raw> {
+    if key_len == 0 {
+        return Vec::new();
+    }
+
+    let mut blocks = vec![Vec::new(); key_len];
+
+    for (i, b) in raw.iter().enumerate() {
+        blocks[i % key_len].push(*b);
+    }
+
+    blocks.into_iter().map(Raw::new).collect()
+}
+
+/// a key some of whose bytes are known, e.g. from the stretch of it a crib covers
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartialKey {
+    bytes: Vec<Option<u8>>,
+}
+
+impl PartialKey {
+    /// a key_len key with nothing known yet
+    pub fn new(key_len: usize) -> Self {
+        Self { bytes: vec![None; key_len] }
+    }
 
-        guesses.push(best.1);
+    /// the full key length, known or not
+    pub fn len(&self) -> usize {
+        self.bytes.len()
     }
 
-    guesses
+    /// true for a zero length key
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// the key byte at position i, None if it isn't known or i is past the end
+    pub fn get(&self, i: usize) -> Option<u8> {
+        self.bytes.get(i).copied().flatten()
+    }
+
+    /// how many positions are known
+    pub fn known(&self) -> usize {
+        self.bytes.iter().filter(|b| b.is_some()).count()
+    }
+
+    /// the whole key, None unless every position is known
+    pub fn to_raw(&self) -> Option<Raw> {
+        self.bytes.iter().copied().collect()
+    }
+
+    /// the key with the unknown positions set to fill, for a trial decryption that only reads
+    /// the known ones
+    pub fn fill(&self, fill: u8) -> Raw {
+        self.bytes.iter().map(|b| b.unwrap_or(fill)).collect()
+    }
+}
+
+/// known plaintext attack: assume crib shows up in the plaintext at each offset in turn and
+/// xor it against the cipher there to get the key bytes it implies. Entry i of the result is
+/// the key_len byte PartialKey (aligned to key positions, so key[j] xors cipher bytes j,
+/// j + key_len, ...) implied by the crib sitting at offset i, knowing only the positions the
+/// crib reaches. The entry is None when a crib longer than the key asks for two different bytes
+/// at one position. Empty if key_len is 0 or the crib doesn't fit in the cipher
+pub fn recover_key_from_crib(cipher: &Raw, crib: &Raw, key_len: usize) -> Vec<Option<PartialKey>> {
+    if key_len == 0 || crib.is_empty() || crib.len() > cipher.len() {
+        return Vec::new();
+    }
+
+    (0..=cipher.len() - crib.len())
+        .map(|offset| {
+            let mut key = PartialKey::new(key_len);
+
+            for (i, c) in crib.iter().enumerate() {
+                let k = cipher[offset + i] ^ c;
+                let slot = &mut key.bytes[(offset + i) % key_len];
+
+                match slot {
+                    Some(existing) if *existing != k => return None,
+                    _ => *slot = Some(k),
+                }
+            }
+
+            Some(key)
+        })
+        .collect()
+}
+
+#[cfg(feature = "std")]
+/// break a repeating xor cipher by guessing the key length and solving each key position as a
+/// single byte xor, returning the (key, plaintext) pair if a key length could be found
+pub fn break_repeating_xor(raw: &Raw, llim: usize, hlim: usize) -> Option<(Raw, Raw)> {
+    break_repeating_xor_with(raw, llim, hlim, english_score)
+}
+
+/// break a repeating xor cipher, solving each key position with the given scorer (lower is
+/// better). Note that every key position sees only every key_len'th byte of the plaintext, so
+/// scorers that look at runs of bytes (like quadgram_score) won't help here
+pub fn break_repeating_xor_with(
+    raw: &Raw,
+    llim: usize,
+    hlim: usize,
+    scorer: impl Fn(&Raw) -> f32,
+) -> Option<(Raw, Raw)> {
+    let key_len = *find_key_len(raw, llim, hlim).first()?;
+
+    Some(break_with_key_len_with(raw, key_len, scorer))
+}
+
+#[cfg(feature = "std")]
+/// break a repeating xor cipher assuming the key is key_len bytes long, returning the (key,
+/// plaintext) pair. Useful for trying the candidates from find_key_len_scored one by one when
+/// the best guess is wrong. Panics if key_len is 0
+pub fn break_with_key_len(raw: &Raw, key_len: usize) -> (Raw, Raw) {
+    break_with_key_len_with(raw, key_len, english_score)
+}
+
+/// break a repeating xor cipher assuming the key is key_len bytes long, solving each key position
+/// with the given scorer (lower is better). Panics if key_len is 0
+pub fn break_with_key_len_with(raw: &Raw, key_len: usize, scorer: impl Fn(&Raw) -> f32) -> (Raw, Raw) {
+    let (key, plaintext, _) = break_columns(raw, key_len, |_| true, scorer);
+
+    (key, plaintext)
+}
+
+#[cfg(feature = "std")]
+/// break_repeating_xor, but also returning the english_score each key byte won its column with
+/// (lower is better). A column scoring far worse than the others is the likely wrong key byte
+pub fn break_repeating_xor_diagnostic(raw: &Raw, llim: usize, hlim: usize) -> Option<(Raw, Raw, Vec<f32>)> {
+    let key_len = *find_key_len(raw, llim, hlim).first()?;
+
+    Some(break_columns(raw, key_len, |_| true, english_score))
+}
+
+/// solve each key position of a key_len repeating xor as a single byte xor, only trying the key
+/// bytes allowed accepts, returning the key, plaintext and each position's winning score. Ties go
+/// to the lower key byte. Panics if key_len is 0 or allowed accepts nothing
+fn break_columns(
+    raw: &Raw,
+    key_len: usize,
+    allowed: impl Fn(u8) -> bool,
+    scorer: impl Fn(&Raw) -> f32,
+) -> (Raw, Raw, Vec<f32>) {
+    assert!(key_len > 0, "key_len must be at least 1");
+
+    let candidates: Vec<u8> = (0..=255u8).filter(|k| allowed(*k)).collect();
+    assert!(!candidates.is_empty(), "allowed must accept at least one key byte");
+
+    // every position gets a column, even if it's empty because the input is short
+    let (key, scores): (Vec<u8>, Vec<f32>) = transpose(raw, key_len)
+        .iter()
+        .map(|block| {
+            candidates
+                .iter()
+                .map(|k| (*k, scorer(&xor_byte(block, *k))))
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .expect("candidates is not empty")
+        })
+        .unzip();
+
+    let key = Raw::new(key);
+    let plaintext = xor(raw, &key).expect("key_len is not 0");
+
+    (key, plaintext, scores)
+}
+
+#[cfg(feature = "std")]
+/// break a repeating xor cipher of a known key length when the key is known to only use certain
+/// bytes (e.g. u8::is_ascii_alphabetic), only trying key bytes that allowed accepts. Faster than
+/// break_with_key_len, and can't be talked into an impossible key byte by a noisy column. None if
+/// allowed accepts nothing. Panics if key_len is 0
+pub fn break_with_key_len_constrained(raw: &Raw, key_len: usize, allowed: impl Fn(u8) -> bool) -> Option<(Raw, Raw)> {
+    break_with_key_len_constrained_with(raw, key_len, allowed, english_score)
+}
+
+/// break_with_key_len_constrained, ranking each position's allowed key bytes with the given
+/// scorer (lower is better). None if allowed accepts nothing. Panics if key_len is 0
+pub fn break_with_key_len_constrained_with(
+    raw: &Raw,
+    key_len: usize,
+    allowed: impl Fn(u8) -> bool,
+    scorer: impl Fn(&Raw) -> f32,
+) -> Option<(Raw, Raw)> {
+    assert!(key_len > 0, "key_len must be at least 1");
+
+    if !(0..=255u8).any(&allowed) {
+        return None;
+    }
+
+    let (key, plaintext, _) = break_columns(raw, key_len, allowed, scorer);
+
+    Some((key, plaintext))
 }
 
+#[cfg(test)]
 mod test {
     use super::*;
 
+    const LYRICS: &str = "Burning 'em, if you ain't quick and nimble\n\
+                          I go crazy when I hear a cymbal\n\
+                          and a high hat with a souped up tempo\n\
+                          I'm on a roll, it's time to go solo\n\
+                          Rollin' in my five point oh\n\
+                          With my ragtop down so my hair can blow";
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_xor_wide() {
+        let seq = Raw::new((0..100u32).map(|i| (i * 7 + i / 3) as u8).collect::<Vec<u8>>());
+        let key = Raw::new(b"YELLOW S".to_vec());
+
+        assert_eq!(xor_wide(&seq, &key), xor(&seq, &key));
+        assert_eq!(xor_wide(&seq, &Raw::new(vec![])), Err(EmptyKey));
+    }
+
+    #[test]
+    fn test_xor_ct() {
+        let seq = Raw::new((0..1000u32).map(|i| (i * 7 + i / 3) as u8).collect::<Vec<u8>>());
+
+        for key_len in 1..=17 {
+            let key = Raw::new((0..key_len).map(|i| 0x5a ^ (i * 31) as u8).collect::<Vec<u8>>());
+
+            for len in [0, 1, 16, 17, 999, 1000] {
+                let part: Raw = seq.get()[..len].into();
+                assert_eq!(xor_ct(&part, &key), xor(&part, &key));
+            }
+        }
+
+        assert_eq!(xor_ct(&seq, &Raw::new(vec![])), Err(EmptyKey));
+    }
+
+    #[test]
+    fn test_xor_matches_byte_at_a_time() {
+        let seq: Raw = (0..1000u32).map(|i| (i * 7) as u8).collect();
+
+        // key lengths that divide, share a factor with and are coprime to the word size, and
+        // sequences shorter than, equal to and around a block (a 1001 byte key never fills one)
+        for key_len in [1, 2, 3, 4, 5, 6, 8, 12, 13, 16, 100, 1001] {
+            let key: Raw = (0..key_len).map(|i| (i * 31 + 1) as u8).collect();
+
+            for len in [0, 1, key_len, 7, 8, 24, 103, 1000] {
+                let seq = seq.slice(0..len.min(seq.len())).unwrap();
+                let expected: Raw = seq.iter().enumerate().map(|(i, b)| b ^ key[i % key.len()]).collect();
+
+                assert_eq!(xor(&seq, &key).unwrap(), expected, "key {key_len} seq {len}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_xor_keystream() {
+        let raw_seq: Raw = Ascii::new(LYRICS.to_string()).unwrap().into();
+        let raw_key: Raw = Ascii::new("ICE".to_string()).unwrap().into();
+
+        assert_eq!(xor_keystream(&raw_seq, raw_key.iter().cycle().copied()), xor(&raw_seq, &raw_key).ok());
+
+        // a counter keystream, one that runs out early and one that runs out exactly at the end
+        let counter = xor_keystream(&Raw::new(vec![0; 4]), 0u8..);
+        assert_eq!(counter, Some(Raw::new(vec![0, 1, 2, 3])));
+        assert_eq!(xor_keystream(&raw_seq, [0u8; 5].into_iter()), None);
+        assert_eq!(xor_keystream(&raw_seq.slice(0..5).unwrap(), [0u8; 5].into_iter()), raw_seq.slice(0..5));
+        assert_eq!(xor_keystream(&Raw::new(vec![]), core::iter::empty()), Some(Raw::new(vec![])));
+    }
+
+    #[test]
+    fn test_encrypt_to_hex() {
+        let hex = encrypt_to_hex("Burning 'em, if you ain't quick and nimble\nI go crazy when I hear a cymbal", "ICE").unwrap();
+        assert_eq!(
+            hex.as_str(),
+            "0b3637272a2b2e63622c2e69692a23693a2a3c6324202d623d63343c2a26226324272765272a282b2f20430a652e2c652a3124333a653e2b2027630c692b20283165286326302e27282f"
+        );
+
+        assert_eq!(encrypt_to_hex("café", "ICE"), None);
+        assert_eq!(encrypt_to_hex("cafe", "ÏCE"), None);
+        assert_eq!(encrypt_to_hex("cafe", ""), None);
+    }
+
+    #[test]
+    fn test_xor_with_passphrase() {
+        let raw_seq: Raw = Ascii::new(LYRICS.to_string()).unwrap().into();
+        let raw_key: Raw = Ascii::new("ICE".to_string()).unwrap().into();
+
+        assert_eq!(xor_with_passphrase(&raw_seq, "ICE"), xor(&raw_seq, &raw_key));
+        assert_eq!(xor_with_passphrase(&raw_seq, ""), Err(EmptyKey));
+    }
+
+    #[test]
+    fn test_xor_stream() {
+        let raw_seq: Raw = Ascii::new(LYRICS.to_string()).unwrap().into();
+        let raw_key: Raw = Ascii::new("ICE".to_string()).unwrap().into();
+
+        // chunk boundaries that don't line up with the key length
+        let chunks: Vec<&[u8]> = raw_seq.get().chunks(7).collect();
+        let stream = chunks.into_iter().flat_map(|c| c.iter().cloned());
+
+        let streamed: Vec<u8> = xor_stream(stream, raw_key.get()).unwrap().collect();
+        assert_eq!(Raw::new(streamed), xor(&raw_seq, &raw_key).unwrap());
+
+        assert!(xor_stream(raw_seq.iter().cloned(), &[]).is_err());
+    }
+
+    #[test]
+    fn test_fixed_xor() {
+        let a: Raw = Hex::new("1c0111001f010100061a024b53535009181c".to_string()).unwrap().into();
+        let b: Raw = Hex::new("686974207468652062756c6c277320657965".to_string()).unwrap().into();
+        let expected: Raw = Hex::new("746865206b696420646f6e277420706c6179".to_string()).unwrap().into();
+
+        assert_eq!(fixed_xor(&a, &b), Ok(expected));
+        assert_eq!(fixed_xor(&a, &Raw::new(vec![0xff])), Err(LenMismatch { left: 18, right: 1 }));
+        assert_eq!(fixed_xor(&Raw::new(vec![]), &Raw::new(vec![])), Ok(Raw::new(vec![])));
+    }
+
+    #[test]
+    fn test_recover_key_from_crib() {
+        let raw_seq: Raw = Ascii::new(LYRICS.to_string()).unwrap().into();
+        let raw_key: Raw = Ascii::new("VANILLA".to_string()).unwrap().into();
+        let cipher = xor(&raw_seq, &raw_key).unwrap();
+
+        // longer than the key, so it has to agree with itself
+        let crib: Raw = Ascii::new("cymbal\nand a".to_string()).unwrap().into();
+        let at = LYRICS.find("cymbal").unwrap();
+
+        let keys = recover_key_from_crib(&cipher, &crib, 7);
+        assert_eq!(keys.len(), cipher.len() - crib.len() + 1);
+        assert_eq!(keys[at].as_ref().and_then(PartialKey::to_raw), Some(raw_key));
+
+        // every wrong guess contradicts itself
+        let consistent: Vec<usize> = (0..keys.len()).filter(|i| keys[*i].is_some()).collect();
+        assert_eq!(consistent, vec![at]);
+
+        // a short crib only knows the positions it covers, a real 0x00 key byte would still show
+        let partial = recover_key_from_crib(&cipher, &Raw::new(b"Burn".to_vec()), 7)[0].clone().unwrap();
+        assert_eq!((partial.len(), partial.known()), (7, 4));
+        assert_eq!((partial.get(3), partial.get(4), partial.get(7)), (Some(b'I'), None, None));
+        assert_eq!(partial.to_raw(), None);
+        assert_eq!(partial.fill(b'?'), Raw::new(b"VANI???".to_vec()));
+
+        assert!(recover_key_from_crib(&cipher, &crib, 0).is_empty());
+        assert!(recover_key_from_crib(&crib, &cipher, 7).is_empty());
+    }
+
+    #[test]
+    fn test_xor_assign() {
+        let raw_seq: Raw = Ascii::new(LYRICS.to_string()).unwrap().into();
+        let raw_key: Raw = Ascii::new("ICE".to_string()).unwrap().into();
+
+        let mut buf = raw_seq.clone();
+        buf.xor_assign(&raw_key).unwrap();
+        assert_eq!(buf, xor(&raw_seq, &raw_key).unwrap());
+
+        // xor is its own inverse
+        buf.xor_assign(&raw_key).unwrap();
+        assert_eq!(buf, raw_seq);
+
+        assert_eq!(buf.xor_assign(&Raw::new(vec![])), Err(EmptyKey));
+        assert_eq!(buf, raw_seq);
+    }
+
+    #[test]
+    fn test_bitxor() {
+        let a = Raw::new(vec![0x0f, 0xf0, 0xaa]);
+        let b = Raw::new(vec![0xff, 0xff, 0xaa]);
+
+        assert_eq!(&a ^ &b, Raw::new(vec![0xf0, 0x0f, 0x00]));
+        assert_eq!(&a ^ &b, fixed_xor(&a, &b).unwrap());
+        assert_eq!(&(&a ^ &b) ^ &b, a);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bitxor_len_mismatch() {
+        let _ = &Raw::new(vec![0x01, 0x02]) ^ &Raw::new(vec![0x01]);
+    }
+
+    #[test]
+    fn test_xor_cipher() {
+        let raw_seq: Raw = Ascii::new(LYRICS.to_string()).unwrap().into();
+        let raw_key: Raw = Ascii::new("ICE".to_string()).unwrap().into();
+
+        let cipher = XorCipher::new(raw_key.clone()).unwrap();
+        let encrypted = cipher.encrypt(&raw_seq);
+
+        assert_eq!(encrypted, xor(&raw_seq, &raw_key).unwrap());
+        assert_eq!(cipher.decrypt(&encrypted), raw_seq);
+        assert_eq!(cipher.key(), &raw_key);
+
+        assert_eq!(XorCipher::new(Raw::new(vec![])), Err(EmptyKey));
+    }
+
+    #[test]
+    fn test_format_key_alignment() {
+        let cipher = Raw::new((0..18u8).collect::<Vec<u8>>());
+        let key: Raw = Ascii::new("ICE".to_string()).unwrap().into();
+
+        assert_eq!(
+            format_key_alignment(&cipher, &key).unwrap(),
+            "00000000  00 01 02 03 04 05 06 07 08 09 0a 0b 0c 0d 0e 0f\n      \
+             key 49 43 45 49 43 45 49 43 45 49 43 45 49 43 45 49\n\
+             00000010  10 11\n      \
+             key 43 45\n"
+        );
+
+        assert_eq!(format_key_alignment(&Raw::new(vec![]), &key), Ok(String::new()));
+        assert_eq!(format_key_alignment(&cipher, &Raw::new(vec![])), Err(EmptyKey));
+    }
+
+    #[test]
+    fn test_xor_byte() {
+        let seq = Raw::new(vec![0x00, 0x0f, 0xf0, 0xff]);
+
+        assert_eq!(xor_byte(&seq, 0x0f), Raw::new(vec![0x0f, 0x00, 0xff, 0xf0]));
+        assert_eq!(xor_byte(&seq, 0x5a), xor(&seq, &Raw::new(vec![0x5a])).unwrap());
+    }
+
     #[test]
     fn test_find_key_len() {
         let seq = "Secret Message!".to_string();
@@ -70,9 +906,399 @@ mod test {
         let raw_seq: Raw = Ascii::new(seq).unwrap().into();
         let raw_key: Raw = Ascii::new(key).unwrap().into();
 
-        let encrypted_seq = xor(&raw_seq, &raw_key);
+        let encrypted_seq = xor(&raw_seq, &raw_key).unwrap();
 
         let key_len = find_key_len(&encrypted_seq, 1, 5);
         assert_eq!(key_len[0], 3);
     }
+
+    #[test]
+    fn test_find_key_len_multi_block() {
+        // comparing only the first two blocks picks 5 here
+        let raw_seq: Raw = Ascii::new(LYRICS.to_string()).unwrap().into();
+        let raw_key: Raw = Ascii::new("ICE".to_string()).unwrap().into();
+
+        let key_len = find_key_len(&xor(&raw_seq, &raw_key).unwrap(), 2, 10);
+        assert_eq!(key_len[0], 3);
+    }
+
+    #[test]
+    fn test_find_key_len_scored() {
+        let raw_seq: Raw = Ascii::new(LYRICS.to_string()).unwrap().into();
+        let raw_key: Raw = Ascii::new("ICE".to_string()).unwrap().into();
+
+        let scored = find_key_len_scored(&xor(&raw_seq, &raw_key).unwrap(), 2, 10);
+        assert_eq!(scored.len(), 9);
+        assert_eq!(scored[0].0, 3);
+        assert!(scored.windows(2).all(|w| w[0].1 <= w[1].1));
+
+        // find_key_len is the same ranking without the scores
+        assert_eq!(find_key_len(&xor(&raw_seq, &raw_key).unwrap(), 2, 10)[0], scored[0].0);
+    }
+
+    #[test]
+    fn test_find_key_len_unique() {
+        let raw_seq: Raw = Ascii::new(LYRICS.to_string()).unwrap().into();
+        let raw_key: Raw = Ascii::new("ICE".to_string()).unwrap().into();
+        let cipher = xor(&raw_seq, &raw_key).unwrap();
+
+        // with llim above 0 every guess is still a real length, reported once
+        for (llim, hlim) in [(3, 10), (3, 100)] {
+            let guesses = find_key_len(&cipher, llim, hlim);
+
+            assert!(!guesses.is_empty());
+            assert!(guesses.iter().all(|n| (llim..=hlim).contains(n)));
+            assert!(guesses.iter().enumerate().all(|(i, n)| !guesses[..i].contains(n)));
+        }
+
+        assert_eq!(find_key_len(&cipher, 3, 10)[0], 3);
+    }
+
+    #[test]
+    fn test_find_key_len_best() {
+        let raw_seq: Raw = Ascii::new(LYRICS.to_string()).unwrap().into();
+        let raw_key: Raw = Ascii::new("ICE".to_string()).unwrap().into();
+        let cipher = xor(&raw_seq, &raw_key).unwrap();
+
+        let scored = find_key_len_scored(&cipher, 2, 10);
+        let margin = scored[1].1 / scored[0].1;
+
+        assert_eq!(find_key_len_best(&cipher, 2, 10, 1.0), Some(3));
+        assert_eq!(find_key_len_best(&cipher, 2, 10, margin * 0.99), Some(3));
+        assert_eq!(find_key_len_best(&cipher, 2, 10, margin * 1.01), None);
+
+        // a lone candidate has nothing to lose against
+        assert_eq!(find_key_len_best(&cipher, 3, 3, 100.0), Some(3));
+        assert_eq!(find_key_len_best(&Raw::new(vec![0x01]), 2, 10, 1.0), None);
+
+        // all zeros, every length scores the same
+        assert_eq!(find_key_len_best(&Raw::new(vec![0x00; 64]), 2, 10, 1.0), None);
+    }
+
+    #[test]
+    fn test_key_len_profile() {
+        let raw_seq: Raw = Ascii::new(LYRICS.to_string()).unwrap().into();
+        let raw_key: Raw = Ascii::new("ICE".to_string()).unwrap().into();
+        let cipher = xor(&raw_seq, &raw_key).unwrap();
+
+        let profile = key_len_profile(&cipher, 2, 200);
+        assert_eq!(profile.len(), 199);
+
+        // lowest at n = 3 among the short lengths (longer ones get only a few noisy pairs of
+        // blocks out of 216 bytes), and past half the input nothing can be compared
+        let best = profile[..9].iter().enumerate().min_by(|l, r| l.1.total_cmp(r.1)).unwrap().0;
+        assert_eq!(best + 2, 3);
+        assert!(profile[..(cipher.len() / 2 - 1)].iter().all(|d| d.is_finite()));
+        assert!(profile[(cipher.len() / 2 - 1)..].iter().all(|d| d.is_infinite()));
+
+        // find_key_len_scored is the same distances, ranked
+        for (n, d) in find_key_len_scored(&cipher, 2, 200) {
+            assert_eq!(profile[n - 2], d);
+        }
+    }
+
+    #[test]
+    fn test_find_key_len_zero_llim() {
+        let raw_seq: Raw = Ascii::new(LYRICS.to_string()).unwrap().into();
+        let raw_key: Raw = Ascii::new("ICE".to_string()).unwrap().into();
+        let cipher = xor(&raw_seq, &raw_key).unwrap();
+
+        // 0 is never a key length, so a llim of 0 means the same as 1 everywhere
+        assert_eq!(find_key_len_scored(&cipher, 0, 10), find_key_len_scored(&cipher, 1, 10));
+        assert_eq!(find_key_len(&cipher, 0, 10)[0], 3);
+        assert_eq!(find_key_len_best(&cipher, 0, 10, 1.0), find_key_len_best(&cipher, 1, 10, 1.0));
+
+        let profile = key_len_profile(&cipher, 0, 3);
+        assert!(profile[0].is_infinite());
+        assert_eq!(profile[1..], key_len_profile(&cipher, 1, 3)[..]);
+
+        assert!(find_key_len_scored(&cipher, 0, 0).is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_break_zero_llim() {
+        let raw_seq: Raw = Ascii::new(LYRICS.to_string()).unwrap().into();
+        let raw_key: Raw = Ascii::new("ICE".to_string()).unwrap().into();
+        let cipher = xor(&raw_seq, &raw_key).unwrap();
+
+        assert_eq!(find_key_len_ioc(&cipher, 0, 10), find_key_len_ioc(&cipher, 1, 10));
+        assert_eq!(find_key_len_combined(&cipher, 0, 10), find_key_len_combined(&cipher, 1, 10));
+        assert_eq!(break_repeating_xor(&cipher, 0, 10), Some((raw_key, raw_seq)));
+    }
+
+    #[test]
+    fn test_find_key_len_scored_clamped() {
+        let raw = Raw::new(b"twelve bytes".to_vec());
+
+        // only 2..=6 can fit two blocks into 12 bytes
+        let scored = find_key_len_scored(&raw, 2, 100);
+        assert_eq!(scored.len(), 5);
+        assert!(scored.iter().all(|(n, d)| *n <= 6 && d.is_finite()));
+
+        assert!(find_key_len_scored(&raw, 7, 100).is_empty());
+        assert!(find_key_len(&raw, 7, 100).is_empty());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_find_key_len_scored_parallel() {
+        let raw_seq: Raw = Ascii::new(LYRICS.to_string()).unwrap().into();
+        let raw_key: Raw = Ascii::new("ICE".to_string()).unwrap().into();
+        let cipher = xor(&raw_seq, &raw_key).unwrap();
+
+        let mut sequential: Vec<(usize, f32)> = (1..=40).map(|n| (n, key_len_distance(&cipher, n))).collect();
+        sequential.sort_by(|l, r| l.1.total_cmp(&r.1));
+
+        assert_eq!(find_key_len_scored(&cipher, 1, 40), sequential);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_find_key_len_ioc() {
+        let raw_seq: Raw = Ascii::new(LYRICS.to_string()).unwrap().into();
+        let raw_key: Raw = Ascii::new("VANILLA".to_string()).unwrap().into();
+        let cipher = xor(&raw_seq, &raw_key).unwrap();
+
+        let scored = find_key_len_ioc(&cipher, 2, 10);
+        assert_eq!(scored.len(), 9);
+        assert_eq!(scored[0].0, 7);
+
+        // plaintext scores as English at any length
+        assert!((find_key_len_ioc(&raw_seq, 1, 1)[0].1 - 0.0667).abs() < 0.02);
+
+        // past half the input columns get too short to score, so those lengths are left out
+        let scored = find_key_len_ioc(&cipher, 2, 300);
+        assert_eq!(scored.len(), cipher.len() / 2 - 1);
+        assert!(scored.iter().all(|(n, _)| *n <= cipher.len() / 2));
+        // multiples of the key length line the columns up too
+        assert_eq!(scored[0].0 % 7, 0);
+
+        let raw = Raw::new(b"twelve bytes".to_vec());
+        assert_eq!(find_key_len_ioc(&raw, 2, 100).len(), 5);
+        assert!(find_key_len_ioc(&raw, 7, 100).is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_find_key_len_combined() {
+        let raw_seq: Raw = Ascii::new(LYRICS.to_string()).unwrap().into();
+
+        for key in ["ICE", "VANILLA"] {
+            let raw_key: Raw = Ascii::new(key.to_string()).unwrap().into();
+            let cipher = xor(&raw_seq, &raw_key).unwrap();
+
+            let combined = find_key_len_combined(&cipher, 2, 10);
+            assert_eq!(combined.len(), 9);
+            assert_eq!(combined[0].0, key.len());
+            assert!(combined.windows(2).all(|w| w[0].1 <= w[1].1));
+        }
+
+        let cipher = xor(&raw_seq, &Raw::new(b"ICE".to_vec())).unwrap();
+
+        // all the weight on one metric gives back its ranking
+        let hamming: Vec<usize> = find_key_len_scored(&cipher, 2, 10).iter().map(|(n, _)| *n).collect();
+        let weighted: Vec<usize> = find_key_len_weighted(&cipher, 2, 10, 1.0).iter().map(|(n, _)| *n).collect();
+        assert_eq!(weighted, hamming);
+
+        assert!(find_key_len_combined(&Raw::new(vec![0x01, 0x02]), 2, 10).is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_break_single_byte() {
+        let cipher: Raw = Hex::new("1b37373331363f78151b7f2b783431333d78397828372d363c78373e783a393b3736".to_string())
+            .unwrap()
+            .into();
+
+        let (key, plain, _) = break_single_byte(&cipher);
+        let plain: Ascii = plain.into();
+
+        assert_eq!(key, b'X');
+        assert_eq!(plain, Ascii::new("Cooking MC's like a pound of bacon".to_string()).unwrap());
+
+        let (key, _, _) = break_single_byte_with(&cipher, quadgram_score);
+        assert_eq!(key, b'X');
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_break_single_byte_topn() {
+        let cipher: Raw = Hex::new("1b37373331363f78151b7f2b783431333d78397828372d363c78373e783a393b3736".to_string())
+            .unwrap()
+            .into();
+
+        let top = break_single_byte_topn(&cipher, 3);
+        assert_eq!(top.len(), 3);
+        assert_eq!(top[0], break_single_byte(&cipher));
+        assert!(top[0].2 <= top[1].2 && top[1].2 <= top[2].2);
+
+        assert_eq!(break_single_byte_topn(&cipher, 1000).len(), 256);
+        assert!(break_single_byte_topn(&cipher, 0).is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_break_repeating_xor() {
+        let seq = LYRICS;
+        let key = "VANILLA";
+
+        let raw_seq: Raw = Ascii::new(seq.to_string()).unwrap().into();
+        let raw_key: Raw = Ascii::new(key.to_string()).unwrap().into();
+
+        // length isn't a multiple of the key
+        assert_ne!(raw_seq.len() % raw_key.len(), 0);
+
+        let (found_key, plaintext) = break_repeating_xor(&xor(&raw_seq, &raw_key).unwrap(), 2, 10).unwrap();
+        assert_eq!(found_key, raw_key);
+        assert_eq!(plaintext, raw_seq);
+
+        // too short to fit any key length twice
+        assert_eq!(break_repeating_xor(&Raw::new(vec![0x01, 0x02]), 2, 4), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_break_with_key_len() {
+        let raw_seq: Raw = Ascii::new(LYRICS.to_string()).unwrap().into();
+        let raw_key: Raw = Ascii::new("VANILLA".to_string()).unwrap().into();
+        let cipher = xor(&raw_seq, &raw_key).unwrap();
+
+        assert_eq!(break_with_key_len(&cipher, 7), (raw_key, raw_seq));
+
+        // the wrong length still gives a key of that length
+        assert_eq!(break_with_key_len(&cipher, 5).0.len(), 5);
+        assert_eq!(break_with_key_len(&Raw::new(vec![0x01]), 3).0.len(), 3);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_break_with_key_len_constrained() {
+        let raw_seq: Raw = Ascii::new(LYRICS.to_string()).unwrap().into();
+        let raw_key: Raw = Ascii::new("VANILLA".to_string()).unwrap().into();
+        let cipher = xor(&raw_seq, &raw_key).unwrap();
+
+        let (key, plaintext) = break_with_key_len_constrained(&cipher, 7, |k| k.is_ascii_uppercase()).unwrap();
+        assert_eq!(key, raw_key);
+        assert_eq!(plaintext, raw_seq);
+
+        // the constraint wins even when it rules out the real key
+        let (key, _) = break_with_key_len_constrained(&cipher, 7, |k| k.is_ascii_digit()).unwrap();
+        assert!(key.iter().all(u8::is_ascii_digit));
+
+        assert_eq!(break_with_key_len_constrained(&cipher, 7, |_| false), None);
+        assert_eq!(break_with_key_len_constrained(&Raw::new(vec![]), 2, |k| k == b'x').unwrap().0, Raw::new(b"xx".to_vec()));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_break_with_key_len_constrained_with() {
+        let raw_seq: Raw = Ascii::new(LYRICS.to_string()).unwrap().into();
+        let raw_key: Raw = Ascii::new("VANILLA".to_string()).unwrap().into();
+        let cipher = xor(&raw_seq, &raw_key).unwrap();
+
+        let (key, plaintext) =
+            break_with_key_len_constrained_with(&cipher, 7, |k| k.is_ascii_uppercase(), english_score).unwrap();
+        assert_eq!(key, raw_key);
+        assert_eq!(plaintext, raw_seq);
+
+        // the scorer is what ranks the allowed bytes, here preferring the smallest first byte
+        let (key, _) = break_with_key_len_constrained_with(&cipher, 7, |k| k.is_ascii_uppercase(), |r| r[0] as f32).unwrap();
+        assert_eq!(key.len(), 7);
+        assert!(key.iter().all(u8::is_ascii_uppercase));
+        assert_ne!(key, raw_key);
+
+        assert_eq!(break_with_key_len_constrained_with(&cipher, 7, |_| false, english_score), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_break_repeating_xor_diagnostic() {
+        let raw_seq: Raw = Ascii::new(LYRICS.to_string()).unwrap().into();
+        let raw_key: Raw = Ascii::new("VANILLA".to_string()).unwrap().into();
+        let cipher = xor(&raw_seq, &raw_key).unwrap();
+
+        let (key, plaintext, scores) = break_repeating_xor_diagnostic(&cipher, 2, 10).unwrap();
+        assert_eq!((key.clone(), plaintext), break_repeating_xor(&cipher, 2, 10).unwrap());
+        assert_eq!(scores.len(), key.len());
+
+        // each score is what that column's key byte gets from english_score
+        let columns = transpose(&cipher, 7);
+        for (i, score) in scores.iter().enumerate() {
+            assert_eq!(*score, english_score(&xor_byte(&columns[i], key[i])));
+        }
+
+        assert_eq!(break_repeating_xor_diagnostic(&Raw::new(vec![0x01]), 2, 10), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[should_panic]
+    fn test_break_with_key_len_zero() {
+        break_with_key_len(&Raw::new(vec![0x01, 0x02]), 0);
+    }
+
+    #[test]
+    fn test_break_repeating_xor_with() {
+        // a base64 payload, which english_score has no reason to favour
+        let lyrics: Raw = Ascii::new(LYRICS.to_string()).unwrap().into();
+        let seq: Raw = Ascii::new(Base64::from(lyrics).into_string()).unwrap().into();
+        let key: Raw = Ascii::new("KEY".to_string()).unwrap().into();
+
+        let not_base64 = |raw: &Raw| raw.iter().filter(|b| !matches!(b, b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'+' | b'/' | b'=')).count() as f32;
+
+        // base64 has a period of 4 that throws off key length guessing, so pin the length
+        let (found_key, plaintext) = break_repeating_xor_with(&xor(&seq, &key).unwrap(), 3, 3, not_base64).unwrap();
+        assert_eq!(found_key, key);
+        assert_eq!(plaintext, seq);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_find_xored_line() {
+        let plaintext: Raw = Ascii::new("Now that the party is jumping\n".to_string()).unwrap().into();
+
+        let mut lines = Vec::new();
+        for seed in 0..8u8 {
+            // noise that doesn't decrypt to anything readable
+            let noise: Vec<u8> = (0..30u8)
+                .map(|i| i.wrapping_mul(37).wrapping_add(seed.wrapping_mul(91)) ^ 0xa5)
+                .collect();
+
+            lines.push(Raw::new(noise));
+        }
+        lines.insert(5, xor_byte(&plaintext, 0x35));
+
+        let (index, key, found, _) = find_xored_line(&lines).unwrap();
+        assert_eq!(index, 5);
+        assert_eq!(key, 0x35);
+        assert_eq!(found, plaintext);
+
+        assert_eq!(find_xored_line(&[]), None);
+    }
+
+    #[test]
+    fn test_transpose() {
+        let raw = Raw::new(vec![0, 1, 2, 3, 4, 5, 6, 7]);
+
+        let blocks = transpose(&raw, 3);
+        assert_eq!(blocks, vec![Raw::new(vec![0, 3, 6]), Raw::new(vec![1, 4, 7]), Raw::new(vec![2, 5])]);
+
+        // more positions than bytes leaves the tail empty
+        let blocks = transpose(&Raw::new(vec![0, 1]), 3);
+        assert_eq!(blocks, vec![Raw::new(vec![0]), Raw::new(vec![1]), Raw::new(vec![])]);
+
+        assert!(transpose(&raw, 0).is_empty());
+    }
+
+    #[test]
+    fn test_xor_empty_key() {
+        let seq = Raw::new(vec![0x01, 0x02, 0x03]);
+
+        assert_eq!(xor(&seq, &Raw::new(vec![])), Err(EmptyKey));
+        assert_eq!(xor(&seq, &Raw::new(vec![0xff])), Ok(Raw::new(vec![0xfe, 0xfd, 0xfc])));
+
+        // an empty sequence is fine, there's just nothing to xor
+        assert_eq!(xor(&Raw::new(vec![]), &Raw::new(vec![0xff])), Ok(Raw::new(vec![])));
+    }
 }
@@ -0,0 +1,16 @@
+//! The most used types, traits and functions in one import:
+//!
+//! ```
+//! use rxor_tools::prelude::*;
+//!
+//! let cipher = xor(&Raw::from_hex_str("4943").unwrap(), &Raw::new(b"K".to_vec())).unwrap();
+//! assert_eq!(Hex::encode(&cipher).as_str(), "0208");
+//! ```
+
+pub use crate::encodings::{Ascii, Base64, Decode, DecodeError, DecodeErrorKind, Encode, Hex, Raw, TryDecode};
+pub use crate::{fixed_xor, xor, xor_byte, EmptyKey, LenMismatch, XorCipher};
+
+#[cfg(feature = "std")]
+pub use crate::analysis::english_score;
+#[cfg(feature = "std")]
+pub use crate::{break_repeating_xor, break_single_byte, find_key_len};
@@ -0,0 +1,138 @@
+//! Serde support, behind the `serde` feature
+//!
+//! Raw serializes as a hex string by default. Use the submodules to pick the encoding per field:
+//!
+//! ```text
+//! #[derive(Serialize, Deserialize)]
+//! struct Cracked {
+//!     key: Raw,
+//!     #[serde(with = "rxor_tools::serde_raw::base64")]
+//!     plaintext: Raw,
+//! }
+//! ```
+//!
+//! The encoded types serialize as their strings and are validated like `new` when deserialized.
+
+use alloc::string::String;
+
+use serde::de::{Deserialize, Deserializer, Error};
+use serde::ser::{Serialize, Serializer};
+
+use crate::{Ascii, Base32, Base64, Base64Url, Binary, Hex, Raw, TryDecode};
+
+/// turn a deserialized string into an encoded type, rejecting it the way new would
+fn validate<'de, D, T>(s: String, new: fn(String) -> Option<T>, name: &str) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    new(s).ok_or_else(|| D::Error::custom(format_args!("invalid {name} string")))
+}
+
+fn decode<'de, D, T>(encoded: T) -> Result<Raw, D::Error>
+where
+    D: Deserializer<'de>,
+    T: TryDecode,
+{
+    encoded.try_decode().map_err(D::Error::custom)
+}
+
+macro_rules! string_serde {
+    ($t:ident, $name:literal) => {
+        impl Serialize for $t {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $t {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                validate::<D, _>(String::deserialize(deserializer)?, $t::new, $name)
+            }
+        }
+    };
+}
+
+string_serde!(Hex, "hex");
+string_serde!(Base64, "base64");
+string_serde!(Base64Url, "base64url");
+string_serde!(Base32, "base32");
+string_serde!(Binary, "binary");
+string_serde!(Ascii, "ascii");
+
+impl Serialize for Raw {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        hex::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Raw {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        hex::deserialize(deserializer)
+    }
+}
+
+/// (de)serialize Raw as a hex string, the default
+pub mod hex {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(raw: &Raw, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(Hex::from(raw.clone()).as_str())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Raw, D::Error> {
+        decode::<D, _>(Hex::deserialize(deserializer)?)
+    }
+}
+
+/// (de)serialize Raw as a padded base64 string
+pub mod base64 {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(raw: &Raw, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(Base64::from(raw.clone()).as_str())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Raw, D::Error> {
+        decode::<D, _>(Base64::deserialize(deserializer)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Cracked {
+        key: Raw,
+        #[serde(with = "crate::serde_raw::base64")]
+        plaintext: Raw,
+    }
+
+    #[test]
+    fn test_serde_raw() {
+        let raw = Raw::new(b"ICE".to_vec());
+
+        assert_eq!(serde_json::to_string(&raw).unwrap(), "\"494345\"");
+        assert_eq!(serde_json::from_str::<Raw>("\"494345\"").unwrap(), raw);
+        assert!(serde_json::from_str::<Raw>("\"49434\"").is_err());
+        assert!(serde_json::from_str::<Raw>("\"zz\"").is_err());
+
+        let cracked = Cracked { key: raw.clone(), plaintext: raw };
+        let json = serde_json::to_string(&cracked).unwrap();
+
+        assert_eq!(json, r#"{"key":"494345","plaintext":"SUNF"}"#);
+        assert_eq!(serde_json::from_str::<Cracked>(&json).unwrap(), cracked);
+    }
+
+    #[test]
+    fn test_serde_encoded() {
+        let hex = Hex::new("0aff".to_string()).unwrap();
+
+        assert_eq!(serde_json::to_string(&hex).unwrap(), "\"0aff\"");
+        assert_eq!(serde_json::from_str::<Hex>("\"0aff\"").unwrap(), hex);
+
+        assert!(serde_json::from_str::<Hex>("\"0g\"").is_err());
+        assert!(serde_json::from_str::<Base64>("\"*\"").is_err());
+        assert!(serde_json::from_str::<Binary>("\"012\"").is_err());
+    }
+}